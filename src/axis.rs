@@ -1,6 +1,6 @@
 //! Utilites related to the axes of a plane.
 
-use std::{fmt, ops::Not, slice};
+use std::{error::Error, fmt, ops::Not, slice, str::FromStr};
 
 /// The axes of a plane.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -60,6 +60,50 @@ impl fmt::Display for Axis {
     }
 }
 
+/// Error when parsing an axis from its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromStrErr {
+    /// The given string is not the name of an axis.
+    UnknownAxis,
+}
+
+impl fmt::Display for FromStrErr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStrErr::UnknownAxis => fmt.write_str("Unknown axis name"),
+        }
+    }
+}
+
+impl Error for FromStrErr {}
+
+/// Parses an axis from its name, as produced by its [`fmt::Display`]
+/// implementation (case-insensitive).
+///
+/// # Examples
+/// ```rust
+/// use gardiz::axis::Axis;
+///
+/// # fn main() {
+/// assert_eq!(Axis::X.to_string().parse(), Ok(Axis::X));
+/// assert_eq!("Y".parse(), Ok(Axis::Y));
+/// # }
+/// ```
+impl FromStr for Axis {
+    type Err = FromStrErr;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("y") {
+            Ok(Axis::Y)
+        } else if input.eq_ignore_ascii_case("x") {
+            Ok(Axis::X)
+        } else {
+            Err(FromStrErr::UnknownAxis)
+        }
+    }
+}
+
 impl Not for Axis {
     type Output = Axis;
 
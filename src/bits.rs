@@ -82,6 +82,98 @@ where
     }
 }
 
+/// Trait for computing absolute distance between two integers as an unsigned
+/// integer. Unlike [`Distance`], this never overflows, even if the numbers are
+/// at opposite bounds of a signed type, since the output is the unsigned
+/// version of the input type.
+pub trait UnsignedDistance {
+    /// Output unsigned number type.
+    type Output;
+
+    /// Computes the absolute (without sign) distance between the given two
+    /// numbers, as an unsigned number.
+    fn unsigned_distance(&self, other: &Self) -> Self::Output;
+}
+
+/// Trait for checked addition and subtraction of unsigned offsets, such as the
+/// ones produced by [`UnsignedDistance`], to a number.
+pub trait UnsignedOffset: UnsignedDistance + Sized {
+    /// Adds the given unsigned offset to this number, returning `None` on
+    /// overflow.
+    fn checked_add_offset(&self, offset: &Self::Output) -> Option<Self>;
+
+    /// Subtracts the given unsigned offset from this number, returning `None`
+    /// on overflow.
+    fn checked_sub_offset(&self, offset: &Self::Output) -> Option<Self>;
+}
+
+macro_rules! unsigned_distance {
+    ($utype:ident, $itype:ident) => {
+        impl UnsignedDistance for $utype {
+            type Output = $utype;
+
+            fn unsigned_distance(&self, other: &Self) -> Self::Output {
+                if self > other {
+                    self - other
+                } else {
+                    other - self
+                }
+            }
+        }
+
+        impl UnsignedDistance for $itype {
+            type Output = $utype;
+
+            fn unsigned_distance(&self, other: &Self) -> Self::Output {
+                if self > other {
+                    self.cast_unsigned().wrapping_sub(other.cast_unsigned())
+                } else {
+                    other.cast_unsigned().wrapping_sub(self.cast_unsigned())
+                }
+            }
+        }
+
+        impl UnsignedOffset for $utype {
+            fn checked_add_offset(
+                &self,
+                offset: &Self::Output,
+            ) -> Option<Self> {
+                self.checked_add(*offset)
+            }
+
+            fn checked_sub_offset(
+                &self,
+                offset: &Self::Output,
+            ) -> Option<Self> {
+                self.checked_sub(*offset)
+            }
+        }
+
+        impl UnsignedOffset for $itype {
+            fn checked_add_offset(
+                &self,
+                offset: &Self::Output,
+            ) -> Option<Self> {
+                self.checked_add_unsigned(*offset)
+            }
+
+            fn checked_sub_offset(
+                &self,
+                offset: &Self::Output,
+            ) -> Option<Self> {
+                self.checked_sub_unsigned(*offset)
+            }
+        }
+    };
+}
+
+unsigned_distance! { u8, i8 }
+unsigned_distance! { u16, i16 }
+unsigned_distance! { u32, i32 }
+unsigned_distance! { u64, i64 }
+unsigned_distance! { u128, i128 }
+unsigned_distance! { usize, isize }
+
 /// Trait for getting the "excess" that is the half of an unsigned type's
 /// maximum value, typically `1000...0000`. Types should not worry with this
 /// trait, but instead implement [`Unsigned`] and [`Bounded`], since there is a
@@ -179,6 +271,8 @@ mod test {
         ExcessToSigned,
         HalfExcess,
         SignedToExcess,
+        UnsignedDistance,
+        UnsignedOffset,
     };
 
     #[test]
@@ -190,6 +284,29 @@ mod test {
         assert_eq!((-2i8).distance(-17), 15);
     }
 
+    #[test]
+    fn unsigned_distance() {
+        assert_eq!(8u8.unsigned_distance(&3), 5);
+        assert_eq!(4u8.unsigned_distance(&7), 3);
+        assert_eq!(5u8.unsigned_distance(&5), 0);
+        assert_eq!((-9i8).unsigned_distance(&1), 10u8);
+        assert_eq!((-2i8).unsigned_distance(&-17), 15u8);
+        assert_eq!((-128i8).unsigned_distance(&127), 255u8);
+        assert_eq!(127i8.unsigned_distance(&-128), 255u8);
+        assert_eq!(i64::MIN.unsigned_distance(&i64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn unsigned_offset() {
+        assert_eq!(250u8.checked_add_offset(&5), Some(255));
+        assert_eq!(250u8.checked_add_offset(&6), None);
+        assert_eq!(5u8.checked_sub_offset(&6), None);
+        assert_eq!((-128i8).checked_add_offset(&255), Some(127));
+        assert_eq!((-127i8).checked_add_offset(&255), None);
+        assert_eq!(127i8.checked_sub_offset(&255), Some(-128));
+        assert_eq!(126i8.checked_sub_offset(&255), None);
+    }
+
     #[test]
     fn half_excesss() {
         assert_eq!(u8::half_excess(), 0x80);
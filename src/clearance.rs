@@ -0,0 +1,240 @@
+//! This module provides clearance maps, for finding paths of agents larger
+//! than a single point.
+//!
+//! The clearance of a point is the size of the largest square of valid
+//! points whose top-left corner is the point. An agent occupies a rectangle
+//! (its footprint) placed relatively to an anchor point, and it fits at a
+//! given anchor if none of the points of the footprint are invalid. The map
+//! precomputes both the clearances and how many invalid points there are
+//! before every point (a summed-area table), so that testing whether an agent
+//! fits takes constant time, and paths of the anchor can be made by the usual
+//! path making functions of [`Graph`](crate::graph::Graph).
+
+#[cfg(test)]
+mod test;
+
+use crate::{coord::Vec2, rect::Rect};
+use num::{CheckedAdd, One, ToPrimitive, Zero};
+use std::{iter, ops::Sub};
+
+/// A map of the clearance of every point inside of some bounds. Points out
+/// of the bounds are considered invalid.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     clearance::ClearanceMap,
+///     coord::Vec2,
+///     direc::{DirecVector, Direction},
+///     graph::Graph,
+///     rect::Rect,
+/// };
+///
+/// # fn main() {
+/// // A corridor two points wide, with a one point wide shortcut.
+/// let valid_points = |point: &Vec2<i32>| {
+///     point.y <= 1 || point.x >= 8 || (point.x == 4 && point.y <= 5)
+/// };
+/// let bounds =
+///     Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 10, y: 6 } };
+/// let clearance = ClearanceMap::new(bounds, valid_points);
+/// assert_eq!(clearance.clearance(&Vec2 { x: 0, y: 0 }), 2);
+/// assert_eq!(clearance.clearance(&Vec2 { x: 4, y: 3 }), 1);
+///
+/// // A monster occupying a 2x2 square, anchored at its top-left point.
+/// let footprint =
+///     Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 2, y: 2 } };
+/// let start = Vec2 { x: 4, y: 0 };
+/// let goal = Vec2 { x: 8, y: 4 };
+/// let path = Graph::new()
+///     .find_path(&start, &goal, &0, clearance.valid_anchors(footprint))
+///     .unwrap();
+/// // The shortcut is too narrow: the monster goes around.
+/// assert_eq!(
+///     path.steps,
+///     vec![
+///         DirecVector { direction: Direction::Right, magnitude: 4 },
+///         DirecVector { direction: Direction::Down, magnitude: 4 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ClearanceMap<T> {
+    bounds: Rect<T>,
+    width: usize,
+    height: usize,
+    clearances: Vec<T>,
+    /// How many invalid points there are above and to the left of each
+    /// point, with one more row and one more column than the bounds.
+    invalid_counts: Vec<usize>,
+}
+
+impl<T> ClearanceMap<T>
+where
+    T: Clone + Ord + Zero + One + CheckedAdd + Sub<Output = T> + ToPrimitive,
+{
+    /// Computes the clearances of the points inside of the given bounds,
+    /// given which points are valid. Takes time proportional to the area of
+    /// the bounds. The end of the bounds is assumed to be representable by
+    /// `T`.
+    pub fn new<F>(bounds: Rect<T>, mut valid_points: F) -> Self
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let xs = coords(&bounds.start.x, &bounds.size.x);
+        let ys = coords(&bounds.start.y, &bounds.size.y);
+        let width = xs.len();
+        let height = ys.len();
+        let mut valid = Vec::with_capacity(width * height);
+        for y in &ys {
+            for x in &xs {
+                valid.push(valid_points(&Vec2 { x: x.clone(), y: y.clone() }));
+            }
+        }
+
+        let mut clearances = vec![T::zero(); width * height];
+        // Goes backwards, so that the points to the right and below of a
+        // point are computed before it.
+        for j in (0 .. height).rev() {
+            for i in (0 .. width).rev() {
+                if !valid[j * width + i] {
+                    continue;
+                }
+                let at = |i: usize, j: usize| {
+                    if i < width && j < height {
+                        clearances[j * width + i].clone()
+                    } else {
+                        T::zero()
+                    }
+                };
+                let smallest =
+                    at(i + 1, j).min(at(i, j + 1)).min(at(i + 1, j + 1));
+                clearances[j * width + i] = smallest + T::one();
+            }
+        }
+
+        let mut invalid_counts = vec![0; (width + 1) * (height + 1)];
+        for j in 0 .. height {
+            for i in 0 .. width {
+                let own = usize::from(!valid[j * width + i]);
+                invalid_counts[(j + 1) * (width + 1) + i + 1] = own
+                    + invalid_counts[j * (width + 1) + i + 1]
+                    + invalid_counts[(j + 1) * (width + 1) + i]
+                    - invalid_counts[j * (width + 1) + i];
+            }
+        }
+
+        Self { bounds, width, height, clearances, invalid_counts }
+    }
+
+    /// The bounds of this map.
+    pub fn bounds(&self) -> &Rect<T> {
+        &self.bounds
+    }
+
+    /// The clearance of the given point, i.e. the size of the largest square
+    /// of valid points whose top-left corner is the given point. Zero if the
+    /// point is invalid or out of the bounds.
+    pub fn clearance(&self, point: &Vec2<T>) -> T {
+        match self.index(point) {
+            Some(index) => self.clearances[index].clone(),
+            None => T::zero(),
+        }
+    }
+
+    /// Tests whether an agent with the given footprint fits when anchored at
+    /// the given point, i.e. whether all the points of the footprint, placed
+    /// relatively to the anchor, are valid. Square footprints are tested
+    /// against the clearance of their corner, and other footprints against the
+    /// counts of invalid points. An empty footprint occupies no points, but it
+    /// only fits at anchors inside of the bounds, so that path searches using
+    /// it stay bounded.
+    pub fn fits(&self, anchor: &Vec2<T>, footprint: &Rect<T>) -> bool {
+        if footprint.is_empty() {
+            return self.index(anchor).is_some();
+        }
+        let corner_x = anchor.x.checked_add(&footprint.start.x);
+        let corner_y = anchor.y.checked_add(&footprint.start.y);
+        let (start_i, start_j) = match (corner_x, corner_y) {
+            (Some(x), Some(y)) => match self.offsets(&Vec2 { x, y }) {
+                Some(offsets) => offsets,
+                None => return false,
+            },
+            _ => return false,
+        };
+        if footprint.size.x == footprint.size.y {
+            // A square fits exactly where the clearance is large enough.
+            let index = start_j * self.width + start_i;
+            return self.clearances[index] >= footprint.size.x;
+        }
+        let (width, height) =
+            match (footprint.size.x.to_usize(), footprint.size.y.to_usize()) {
+                (Some(width), Some(height)) => (width, height),
+                _ => return false,
+            };
+        let (end_i, end_j) =
+            match (start_i.checked_add(width), start_j.checked_add(height)) {
+                (Some(end_i), Some(end_j)) => (end_i, end_j),
+                _ => return false,
+            };
+        if end_i > self.width || end_j > self.height {
+            return false;
+        }
+
+        // The footprint has no invalid points if the counts of invalid points
+        // before its corners cancel out.
+        let count =
+            |i: usize, j: usize| self.invalid_counts[j * (self.width + 1) + i];
+        count(end_i, end_j) + count(start_i, start_j)
+            == count(start_i, end_j) + count(end_i, start_j)
+    }
+
+    /// A function validating the anchors where an agent with the given
+    /// footprint fits (see [`ClearanceMap::fits`]). It can be given as
+    /// `valid_points` to the path making functions, which then make paths of
+    /// the anchor.
+    pub fn valid_anchors(
+        &self,
+        footprint: Rect<T>,
+    ) -> impl Fn(&Vec2<T>) -> bool + '_ {
+        move |anchor| self.fits(anchor, &footprint)
+    }
+
+    /// The index of the given point in the list of clearances, if inside of
+    /// the bounds.
+    fn index(&self, point: &Vec2<T>) -> Option<usize> {
+        let (i, j) = self.offsets(point)?;
+        Some(j * self.width + i)
+    }
+
+    /// The offsets of the given point from the start of the bounds, if inside
+    /// of the bounds.
+    fn offsets(&self, point: &Vec2<T>) -> Option<(usize, usize)> {
+        if point.x < self.bounds.start.x || point.y < self.bounds.start.y {
+            return None;
+        }
+        let i = (point.x.clone() - self.bounds.start.x.clone()).to_usize()?;
+        let j = (point.y.clone() - self.bounds.start.y.clone()).to_usize()?;
+        if i < self.width && j < self.height {
+            Some((i, j))
+        } else {
+            None
+        }
+    }
+}
+
+/// The coordinates from `start` (included), for the given length.
+fn coords<T>(start: &T, length: &T) -> Vec<T>
+where
+    T: Clone + One + CheckedAdd + ToPrimitive,
+{
+    let length = length.to_usize().unwrap_or(0);
+    iter::successors(Some(start.clone()), |coord| coord.checked_add(&T::one()))
+        .take(length)
+        .collect()
+}
@@ -0,0 +1,194 @@
+use super::ClearanceMap;
+use crate::{coord::Vec2, graph::Graph, rect::Rect};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+
+fn random_walls(rng: &mut StdRng) -> BTreeSet<Vec2<i32>> {
+    (0 .. 60)
+        .map(|_| Vec2 { x: rng.gen_range(0 .. 16), y: rng.gen_range(0 .. 12) })
+        .collect()
+}
+
+/// Tests whether a footprint fits at an anchor by checking all its points.
+fn fits_brute<F>(
+    anchor: Vec2<i32>,
+    footprint: &Rect<i32>,
+    mut valid_points: F,
+) -> bool
+where
+    F: FnMut(&Vec2<i32>) -> bool,
+{
+    (0 .. footprint.size.y).all(|y| {
+        (0 .. footprint.size.x).all(|x| {
+            valid_points(&Vec2 {
+                x: anchor.x + footprint.start.x + x,
+                y: anchor.y + footprint.start.y + y,
+            })
+        })
+    })
+}
+
+#[test]
+fn clearance_and_fits() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let bounds =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 16, y: 12 } };
+    for _ in 0 .. 20 {
+        let walls = random_walls(&mut rng);
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 16).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let map = ClearanceMap::new(bounds, valid_points);
+        for y in -2 .. 14 {
+            for x in -2 .. 18 {
+                let point = Vec2 { x, y };
+                let clearance = map.clearance(&point);
+                let square = |side| Rect {
+                    start: Vec2 { x: 0, y: 0 },
+                    size: Vec2 { x: side, y: side },
+                };
+                assert!(fits_brute(point, &square(clearance), valid_points));
+                assert!(!fits_brute(
+                    point,
+                    &square(clearance + 1),
+                    valid_points
+                ));
+
+                for (width, height) in [(1, 1), (2, 2), (3, 3), (3, 1), (1, 4)]
+                {
+                    let centered = Rect {
+                        start: Vec2 { x: -1, y: -1 },
+                        size: Vec2 { x: width, y: height },
+                    };
+                    assert_eq!(
+                        map.fits(&point, &centered),
+                        fits_brute(point, &centered, valid_points)
+                    );
+                }
+                let empty = Rect {
+                    start: Vec2 { x: 0, y: 0 },
+                    size: Vec2 { x: 0, y: 3 },
+                };
+                assert_eq!(
+                    map.fits(&point, &empty),
+                    (0 .. 16).contains(&x) && (0 .. 12).contains(&y)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn corridor_fits_rectangles() {
+    // A horizontal corridor one point tall.
+    let valid_points = |point: &Vec2<i32>| point.y == 2;
+    let bounds =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 10, y: 5 } };
+    let map = ClearanceMap::new(bounds, valid_points);
+    let footprint =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 3, y: 1 } };
+    assert!(map.fits(&Vec2 { x: 0, y: 2 }, &footprint));
+    assert!(map.fits(&Vec2 { x: 7, y: 2 }, &footprint));
+    assert!(!map.fits(&Vec2 { x: 8, y: 2 }, &footprint));
+    assert!(!map.fits(&Vec2 { x: 0, y: 1 }, &footprint));
+
+    let path = Graph::new()
+        .find_path(
+            &Vec2 { x: 0, y: 2 },
+            &Vec2 { x: 7, y: 2 },
+            &0,
+            map.valid_anchors(footprint),
+        )
+        .unwrap();
+    assert_eq!(path.length(), 7);
+
+    let tall = Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 1, y: 2 } };
+    assert!(Graph::new()
+        .find_path(
+            &Vec2 { x: 0, y: 2 },
+            &Vec2 { x: 7, y: 2 },
+            &0,
+            map.valid_anchors(tall),
+        )
+        .is_none());
+}
+
+#[test]
+fn anchor_paths() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let bounds =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 16, y: 12 } };
+    let footprint =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 2, y: 2 } };
+    for _ in 0 .. 30 {
+        let walls = random_walls(&mut rng);
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 16).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let map = ClearanceMap::new(bounds, valid_points);
+        let start =
+            Vec2 { x: rng.gen_range(0 .. 16), y: rng.gen_range(0 .. 12) };
+        let goal =
+            Vec2 { x: rng.gen_range(0 .. 16), y: rng.gen_range(0 .. 12) };
+
+        let path = Graph::new().find_path(
+            &start,
+            &goal,
+            &0,
+            map.valid_anchors(footprint),
+        );
+        let expected = Graph::new().find_path(&start, &goal, &0, |point| {
+            fits_brute(*point, &footprint, valid_points)
+        });
+        assert_eq!(path, expected);
+        if let Some(path) = path {
+            for anchor in path.points().skip(1) {
+                assert!(fits_brute(anchor, &footprint, valid_points));
+            }
+        }
+    }
+}
+
+#[test]
+fn unsigned_bounds() {
+    let bounds =
+        Rect { start: Vec2 { x: 2u8, y: 3 }, size: Vec2 { x: 4, y: 3 } };
+    let map = ClearanceMap::new(bounds, |point| point != &Vec2 { x: 4, y: 4 });
+    assert_eq!(map.clearance(&Vec2 { x: 2, y: 3 }), 2);
+    assert_eq!(map.clearance(&Vec2 { x: 3, y: 3 }), 1);
+    assert_eq!(map.clearance(&Vec2 { x: 2, y: 4 }), 2);
+    assert_eq!(map.clearance(&Vec2 { x: 4, y: 4 }), 0);
+    assert_eq!(map.clearance(&Vec2 { x: 5, y: 3 }), 1);
+    assert_eq!(map.clearance(&Vec2 { x: 0, y: 0 }), 0);
+    assert_eq!(map.clearance(&Vec2 { x: 255, y: 255 }), 0);
+    let footprint =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 2, y: 2 } };
+    assert!(map.fits(&Vec2 { x: 2, y: 4 }, &footprint));
+    assert!(!map.fits(&Vec2 { x: 3, y: 3 }, &footprint));
+    assert!(!map.fits(&Vec2 { x: 255, y: 255 }, &footprint));
+}
+
+#[test]
+fn huge_footprints() {
+    let bounds =
+        Rect { start: Vec2 { x: 0u64, y: 0 }, size: Vec2 { x: 4, y: 4 } };
+    let map = ClearanceMap::new(bounds, |_| true);
+    let wide =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: u64::MAX, y: 1 } };
+    assert!(!map.fits(&Vec2 { x: 1, y: 0 }, &wide));
+    let tall =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 1, y: u64::MAX } };
+    assert!(!map.fits(&Vec2 { x: 0, y: 1 }, &tall));
+    let square = Rect {
+        start: Vec2 { x: 0, y: 0 },
+        size: Vec2 { x: u64::MAX, y: u64::MAX },
+    };
+    assert!(!map.fits(&Vec2 { x: 1, y: 1 }, &square));
+    let square = Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 3, y: 3 } };
+    assert!(map.fits(&Vec2 { x: 1, y: 1 }, &square));
+    assert!(!map.fits(&Vec2 { x: 2, y: 1 }, &square));
+}
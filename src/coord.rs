@@ -5,19 +5,31 @@ mod test;
 
 use crate::{
     axis::Axis,
-    bits::{CastSigned, CastUnsigned, ExcessToSigned, HalfExcess},
+    bits::{
+        CastSigned,
+        CastUnsigned,
+        Distance,
+        ExcessToSigned,
+        HalfExcess,
+        UnsignedOffset,
+    },
     direc::{DirecVector, Direction},
+    rect::Rect,
+    shape::{Metric, Rings, Spiral},
 };
 use num::{
     integer::Roots,
     traits::{
+        AsPrimitive,
         Bounded,
         CheckedAdd,
         CheckedDiv,
+        CheckedEuclid,
         CheckedMul,
         CheckedNeg,
         CheckedRem,
         CheckedSub,
+        Euclid,
         Float,
         Num,
         One,
@@ -37,6 +49,7 @@ use num::{
 use std::{
     borrow::{Borrow, BorrowMut},
     cmp::Ordering,
+    convert::TryFrom,
     error::Error,
     fmt,
     ops::{
@@ -55,6 +68,7 @@ use std::{
         Sub,
         SubAssign,
     },
+    str::FromStr,
 };
 
 /// Generic 2D vector. It could be a coordinate, it could be size, anything like
@@ -306,6 +320,119 @@ impl<T> Vec2<T> {
         prod.y.checked_add(&prod.x)
     }
 
+    /// Computes the perpendicular dot product (AKA 2D cross product) of the
+    /// vector, i.e. `x1 * y2 - y1 * x2`. Please note that on unsigned types
+    /// this might underflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let left: Vec2<i16> = Vec2 { x: 5, y: 3 };
+    /// let right = Vec2 { x: 4, y: 8 };
+    /// assert_eq!(left.perp_dot(right), 5 * 8 - 3 * 4);
+    /// # }
+    /// ```
+    pub fn perp_dot<U, A>(self, other: Vec2<U>) -> A::Output
+    where
+        T: Mul<U, Output = A>,
+        A: Sub,
+    {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Computes the perpendicular dot product of the vector, by reference.
+    pub fn perp_dot_ref<'this, 'other, U, A>(
+        &'this self,
+        other: &'other Vec2<U>,
+    ) -> A::Output
+    where
+        &'this T: Mul<&'other U, Output = A>,
+        A: Sub,
+    {
+        &self.x * &other.y - &self.y * &other.x
+    }
+
+    /// Computes the perpendicular dot product of the vector, wrapping around
+    /// overflow.
+    pub fn wrapping_perp_dot(&self, other: &Self) -> T
+    where
+        T: WrappingSub + WrappingMul,
+    {
+        let left = self.x.wrapping_mul(&other.y);
+        let right = self.y.wrapping_mul(&other.x);
+        left.wrapping_sub(&right)
+    }
+
+    /// Computes the perpendicular dot product of the vector, saturating when
+    /// it overflows.
+    pub fn saturating_perp_dot(&self, other: &Self) -> T
+    where
+        T: SaturatingSub + SaturatingMul,
+    {
+        let left = self.x.saturating_mul(&other.y);
+        let right = self.y.saturating_mul(&other.x);
+        left.saturating_sub(&right)
+    }
+
+    /// Computes the perpendicular dot product of the vector, returning `None`
+    /// if it overflows.
+    pub fn checked_perp_dot(&self, other: &Self) -> Option<T>
+    where
+        T: CheckedSub + CheckedMul,
+    {
+        let left = self.x.checked_mul(&other.y)?;
+        let right = self.y.checked_mul(&other.x)?;
+        left.checked_sub(&right)
+    }
+
+    /// Tests the orientation of the triangle formed by this point, `second`
+    /// and `third`, in this order. Since the Y axis grows downwards (see
+    /// [`Direction::Down`]), a positive perpendicular dot product of the
+    /// differences `second - self` and `third - self` means a clockwise turn.
+    /// Differences are computed through [`Distance`], so this works on unsigned
+    /// types as well, without underflowing. The products of the differences
+    /// are compared without being computed, so they cannot overflow either;
+    /// only the differences themselves must be representable by `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::{Orientation, Vec2};
+    ///
+    /// # fn main() {
+    /// let first: Vec2<u16> = Vec2 { x: 2, y: 2 };
+    /// let second = Vec2 { x: 5, y: 2 };
+    /// assert_eq!(
+    ///     first.orientation(&second, &Vec2 { x: 5, y: 4 }),
+    ///     Orientation::Clockwise
+    /// );
+    /// assert_eq!(
+    ///     first.orientation(&second, &Vec2 { x: 5, y: 0 }),
+    ///     Orientation::CounterClockwise
+    /// );
+    /// assert_eq!(
+    ///     first.orientation(&second, &Vec2 { x: 9, y: 2 }),
+    ///     Orientation::Collinear
+    /// );
+    /// # }
+    /// ```
+    pub fn orientation(&self, second: &Self, third: &Self) -> Orientation
+    where
+        T: Clone + Ord + Zero,
+        T: Sub<Output = T> + Div<Output = T> + Rem<Output = T>,
+    {
+        let first_side = signed_diffs(self, second);
+        let second_side = signed_diffs(self, third);
+        let left = signed_mul(first_side.x, second_side.y);
+        let right = signed_mul(first_side.y, second_side.x);
+        match signed_cmp(left, right) {
+            Ordering::Greater => Orientation::Clockwise,
+            Ordering::Less => Orientation::CounterClockwise,
+            Ordering::Equal => Orientation::Collinear,
+        }
+    }
+
     /// Computes the square of the magnitude of the vector. The formula is:
     /// `x * x + y * y`.
     ///
@@ -471,6 +598,247 @@ impl<T> Vec2<T> {
         Some(squared.sqrt())
     }
 
+    /// Computes the Manhattan distance (AKA taxicab distance) between two
+    /// points, i.e. `|x1 - x2| + |y1 - y2|`. Works on unsigned types as well,
+    /// since the absolute distance of each coordinate is computed through
+    /// [`Distance`].
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let left: Vec2<u16> = Vec2 { x: 5, y: 3 };
+    /// let right = Vec2 { x: 2, y: 8 };
+    /// assert_eq!(left.manhattan_dist(right), 3 + 5);
+    /// # }
+    /// ```
+    pub fn manhattan_dist<A>(self, other: Self) -> A::Output
+    where
+        T: Distance<T, Output = A>,
+        A: Add,
+    {
+        let dist = self.zip_with(other, Distance::distance);
+        dist.y + dist.x
+    }
+
+    /// Computes the Manhattan distance between two points, wrapping around on
+    /// overflow.
+    pub fn wrapping_manhattan_dist(&self, other: &Self) -> T
+    where
+        T: WrappingAdd + WrappingSub + Ord,
+    {
+        let dist = self.as_ref().zip_with(other.as_ref(), wrapping_distance);
+        dist.y.wrapping_add(&dist.x)
+    }
+
+    /// Computes the Manhattan distance between two points, saturating on
+    /// overflow.
+    pub fn saturating_manhattan_dist(&self, other: &Self) -> T
+    where
+        T: SaturatingAdd + SaturatingSub + Ord,
+    {
+        let dist = self.as_ref().zip_with(other.as_ref(), saturating_distance);
+        dist.y.saturating_add(&dist.x)
+    }
+
+    /// Computes the Manhattan distance between two points, returning `None` on
+    /// overflow.
+    pub fn checked_manhattan_dist(&self, other: &Self) -> Option<T>
+    where
+        T: CheckedAdd + CheckedSub + Ord,
+    {
+        let dist = self
+            .as_ref()
+            .zip_with(other.as_ref(), checked_distance)
+            .transpose()?;
+        dist.y.checked_add(&dist.x)
+    }
+
+    /// Computes the Chebyshev distance (AKA chessboard distance) between two
+    /// points, i.e. `max(|x1 - x2|, |y1 - y2|)`. Works on unsigned types as
+    /// well, since the absolute distance of each coordinate is computed
+    /// through [`Distance`].
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let left: Vec2<u16> = Vec2 { x: 5, y: 3 };
+    /// let right = Vec2 { x: 2, y: 8 };
+    /// assert_eq!(left.chebyshev_dist(right), 5);
+    /// # }
+    /// ```
+    pub fn chebyshev_dist<A>(self, other: Self) -> A
+    where
+        T: Distance<T, Output = A>,
+        A: Ord,
+    {
+        let dist = self.zip_with(other, Distance::distance);
+        dist.y.max(dist.x)
+    }
+
+    /// Computes the Chebyshev distance between two points, wrapping around on
+    /// overflow.
+    pub fn wrapping_chebyshev_dist(&self, other: &Self) -> T
+    where
+        T: WrappingSub + Ord,
+    {
+        let dist = self.as_ref().zip_with(other.as_ref(), wrapping_distance);
+        dist.y.max(dist.x)
+    }
+
+    /// Computes the Chebyshev distance between two points, saturating on
+    /// overflow.
+    pub fn saturating_chebyshev_dist(&self, other: &Self) -> T
+    where
+        T: SaturatingSub + Ord,
+    {
+        let dist = self.as_ref().zip_with(other.as_ref(), saturating_distance);
+        dist.y.max(dist.x)
+    }
+
+    /// Computes the Chebyshev distance between two points, returning `None` on
+    /// overflow.
+    pub fn checked_chebyshev_dist(&self, other: &Self) -> Option<T>
+    where
+        T: CheckedSub + Ord,
+    {
+        let dist = self
+            .as_ref()
+            .zip_with(other.as_ref(), checked_distance)
+            .transpose()?;
+        Some(dist.y.max(dist.x))
+    }
+
+    /// Computes the square of the euclidean distance between two points, i.e.
+    /// `(x1 - x2) * (x1 - x2) + (y1 - y2) * (y1 - y2)`. Works on unsigned types
+    /// as well, since the absolute distance of each coordinate is computed
+    /// through [`Distance`].
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let left: Vec2<u16> = Vec2 { x: 5, y: 3 };
+    /// let right = Vec2 { x: 2, y: 8 };
+    /// assert_eq!(left.sqr_euclid_dist(right), 3 * 3 + 5 * 5);
+    /// # }
+    /// ```
+    pub fn sqr_euclid_dist<A, B>(self, other: Self) -> B::Output
+    where
+        T: Distance<T, Output = A>,
+        A: Clone + Mul<Output = B>,
+        B: Add,
+    {
+        self.zip_with(other, Distance::distance).sqr_magnitude()
+    }
+
+    /// Computes the square of the euclidean distance between two points,
+    /// wrapping around on overflow.
+    pub fn wrapping_sqr_euclid_dist(&self, other: &Self) -> T
+    where
+        T: WrappingAdd + WrappingSub + WrappingMul + Ord,
+    {
+        self.as_ref()
+            .zip_with(other.as_ref(), wrapping_distance)
+            .wrapping_sqr_mag()
+    }
+
+    /// Computes the square of the euclidean distance between two points,
+    /// saturating on overflow.
+    pub fn saturating_sqr_euclid_dist(&self, other: &Self) -> T
+    where
+        T: SaturatingAdd + SaturatingSub + SaturatingMul + Ord,
+    {
+        self.as_ref()
+            .zip_with(other.as_ref(), saturating_distance)
+            .saturating_sqr_mag()
+    }
+
+    /// Computes the square of the euclidean distance between two points,
+    /// returning `None` on overflow.
+    pub fn checked_sqr_euclid_dist(&self, other: &Self) -> Option<T>
+    where
+        T: CheckedAdd + CheckedSub + CheckedMul + Ord,
+    {
+        self.as_ref()
+            .zip_with(other.as_ref(), checked_distance)
+            .transpose()?
+            .checked_sqr_mag()
+    }
+
+    /// Computes the minimum of each coordinate. Please note that this is not
+    /// the same as [`Ord::min`], which compares the vectors as a whole.
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let left: Vec2<i16> = Vec2 { x: 5, y: -3 };
+    /// let right = Vec2 { x: 4, y: 8 };
+    /// assert_eq!(left.elemwise_min(right), Vec2 { x: 4, y: -3 });
+    /// # }
+    /// ```
+    pub fn elemwise_min(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.zip_with(other, Ord::min)
+    }
+
+    /// Computes the maximum of each coordinate. Please note that this is not
+    /// the same as [`Ord::max`], which compares the vectors as a whole.
+    pub fn elemwise_max(self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.zip_with(other, Ord::max)
+    }
+
+    /// Clamps each coordinate into the range covered by the given rectangle,
+    /// i.e. returns the point of the rectangle nearest to this point. Returns
+    /// `None` if the rectangle is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use gardiz::{coord::Vec2, rect::Rect};
+    ///
+    /// # fn main() {
+    /// let rect: Rect<i16> = Rect {
+    ///     start: Vec2 { x: -2, y: 3 },
+    ///     size: Vec2 { x: 5, y: 4 },
+    /// };
+    /// assert_eq!(
+    ///     Vec2 { x: 10, y: 0 }.clamp_to(&rect),
+    ///     Some(Vec2 { x: 2, y: 3 })
+    /// );
+    /// assert_eq!(
+    ///     Vec2 { x: 0, y: 4 }.clamp_to(&rect),
+    ///     Some(Vec2 { x: 0, y: 4 })
+    /// );
+    /// # }
+    /// ```
+    pub fn clamp_to<'rect, S, U>(self, rect: &'rect Rect<T, S>) -> Option<Self>
+    where
+        &'rect S: Sub<S, Output = U>,
+        S: One + Zero,
+        &'rect T: Add<U, Output = T> + Sub<T, Output = T>,
+        T: One + Ord + Clone,
+    {
+        if rect.is_empty() {
+            return None;
+        }
+        let end = rect.end_non_empty_ref()?;
+        let clamped = self
+            .zip(rect.start.clone())
+            .zip_with(end, |(this, start), end| this.max(start).min(end));
+        Some(clamped)
+    }
+
     /// Moves this vector in the given direction by one.
     ///
     /// # Examples
@@ -636,6 +1004,26 @@ impl<T> Vec2<T> {
         }
     }
 
+    /// Iterator over the points around this one, ring by ring, in
+    /// nondecreasing distance using the given metric. This one is the first
+    /// point yielded. See [`Rings`] for clipping and limiting the radius.
+    pub fn rings<D>(self, metric: Metric) -> Rings<T, D>
+    where
+        T: UnsignedOffset<Output = D> + Bounded,
+    {
+        Rings::new(self, metric)
+    }
+
+    /// Iterator over the points around this one in a square spiral, up to the
+    /// given Chebyshev distance (inclusive). This one is the first point
+    /// yielded. See [`Spiral`] for clipping.
+    pub fn spiral<D>(self, max_radius: D) -> Spiral<T, D>
+    where
+        T: UnsignedOffset<Output = D> + Bounded,
+    {
+        Spiral::new(self, max_radius)
+    }
+
     /// Useful for showing signed coordinates to humans, when the vector
     /// represents coordinates. Flips the Y coordinate, i.e. inverts the number
     /// line, the greatest value becomes the lesser, the lesser becomes the
@@ -702,6 +1090,168 @@ impl<T> Vec2<T> {
     {
         self.half_exc_to_signed().flip_y()
     }
+
+    /// Tries to convert each coordinate into another type, returning an error
+    /// if some coordinate does not fit in the target type. An error on `x`
+    /// gets priority over an error on `y`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let vector: Vec2<u16> = Vec2 { x: 105, y: 97 };
+    /// assert_eq!(vector.try_cast::<i8>(), Ok(Vec2 { x: 105, y: 97 }));
+    ///
+    /// let vector: Vec2<u16> = Vec2 { x: 105, y: 300 };
+    /// assert!(vector.try_cast::<i8>().is_err());
+    /// # }
+    /// ```
+    pub fn try_cast<U>(self) -> Result<Vec2<U>, U::Error>
+    where
+        U: TryFrom<T>,
+    {
+        self.map(U::try_from).transpose_err_x()
+    }
+
+    /// Converts each coordinate into another primitive type, with the same
+    /// semantics of the `as` operator, i.e. truncating or wrapping around when
+    /// the coordinate does not fit in the target type.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::coord::Vec2;
+    ///
+    /// # fn main() {
+    /// let vector: Vec2<u16> = Vec2 { x: 105, y: 300 };
+    /// assert_eq!(vector.cast_lossy::<u8>(), Vec2 { x: 105, y: 44 });
+    /// # }
+    /// ```
+    pub fn cast_lossy<U>(self) -> Vec2<U>
+    where
+        T: AsPrimitive<U>,
+        U: Copy + 'static,
+    {
+        self.map(AsPrimitive::as_)
+    }
+}
+
+/// Orientation of a sequence of three points. See [`Vec2::orientation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Orientation {
+    /// The points make a clockwise turn.
+    Clockwise,
+    /// The points make a counter-clockwise turn.
+    CounterClockwise,
+    /// The points are in the same line.
+    Collinear,
+}
+
+fn signed_diffs<T>(from: &Vec2<T>, to: &Vec2<T>) -> Vec2<(Ordering, T)>
+where
+    T: Clone + Ord + Sub<Output = T>,
+{
+    from.as_ref().zip_with(to.as_ref(), |from, to| {
+        (to.cmp(from), to.clone().distance(from.clone()))
+    })
+}
+
+/// The sign of a product, together with its factors, which are not multiplied
+/// so that they cannot overflow.
+fn signed_mul<T>(
+    left: (Ordering, T),
+    right: (Ordering, T),
+) -> (Ordering, (T, T)) {
+    let sign = match (left.0, right.0) {
+        (Ordering::Equal, _) | (_, Ordering::Equal) => Ordering::Equal,
+        (left_sign, right_sign) if left_sign == right_sign => Ordering::Greater,
+        _ => Ordering::Less,
+    };
+    (sign, (left.1, right.1))
+}
+
+fn signed_cmp<T>(
+    left: (Ordering, (T, T)),
+    right: (Ordering, (T, T)),
+) -> Ordering
+where
+    T: Clone + Ord + Zero + Div<Output = T> + Rem<Output = T>,
+{
+    match (left.0, right.0) {
+        (Ordering::Equal, Ordering::Equal) => Ordering::Equal,
+        (Ordering::Greater, Ordering::Greater) => cmp_products(left.1, right.1),
+        (Ordering::Less, Ordering::Less) => cmp_products(right.1, left.1),
+        (left_sign, right_sign) => left_sign.cmp(&right_sign),
+    }
+}
+
+/// Compares the products `a * b` and `c * d` of positive numbers without
+/// computing them, by comparing the fractions `a / d` and `c / b` through their
+/// integer parts and remainders, as in Euclid's algorithm.
+fn cmp_products<T>((a, b): (T, T), (c, d): (T, T)) -> Ordering
+where
+    T: Clone + Ord + Zero + Div<Output = T> + Rem<Output = T>,
+{
+    let (mut left_num, mut left_den) = (a, d);
+    let (mut right_num, mut right_den) = (c, b);
+    loop {
+        let left_int = left_num.clone() / left_den.clone();
+        let right_int = right_num.clone() / right_den.clone();
+        if left_int != right_int {
+            return left_int.cmp(&right_int);
+        }
+        let left_rem = left_num % left_den.clone();
+        let right_rem = right_num % right_den.clone();
+        match (left_rem.is_zero(), right_rem.is_zero()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => (),
+        }
+        // The remainders compare as `left_rem / left_den` and
+        // `right_rem / right_den`, which compare as their inverses swapped.
+        left_num = right_den;
+        right_num = left_den;
+        left_den = right_rem;
+        right_den = left_rem;
+    }
+}
+
+fn wrapping_distance<T>(this: &T, other: &T) -> T
+where
+    T: WrappingSub + Ord,
+{
+    if this > other {
+        this.wrapping_sub(other)
+    } else {
+        other.wrapping_sub(this)
+    }
+}
+
+fn saturating_distance<T>(this: &T, other: &T) -> T
+where
+    T: SaturatingSub + Ord,
+{
+    if this > other {
+        this.saturating_sub(other)
+    } else {
+        other.saturating_sub(this)
+    }
+}
+
+fn checked_distance<T>(this: &T, other: &T) -> Option<T>
+where
+    T: CheckedSub + Ord,
+{
+    if this > other {
+        this.checked_sub(other)
+    } else {
+        other.checked_sub(this)
+    }
 }
 
 impl<T> Vec2<Option<T>> {
@@ -941,6 +1491,42 @@ where
     }
 }
 
+impl<T> Euclid for Vec2<T>
+where
+    T: Euclid,
+{
+    fn div_euclid(&self, other: &Self) -> Self {
+        self.as_ref()
+            .zip_with(other.as_ref(), |this, other| this.div_euclid(other))
+    }
+
+    fn rem_euclid(&self, other: &Self) -> Self {
+        self.as_ref()
+            .zip_with(other.as_ref(), |this, other| this.rem_euclid(other))
+    }
+}
+
+impl<T> CheckedEuclid for Vec2<T>
+where
+    T: CheckedEuclid,
+{
+    fn checked_div_euclid(&self, other: &Self) -> Option<Self> {
+        self.as_ref()
+            .zip_with(other.as_ref(), |this, other| {
+                this.checked_div_euclid(other)
+            })
+            .transpose()
+    }
+
+    fn checked_rem_euclid(&self, other: &Self) -> Option<Self> {
+        self.as_ref()
+            .zip_with(other.as_ref(), |this, other| {
+                this.checked_rem_euclid(other)
+            })
+            .transpose()
+    }
+}
+
 /// Error when parsing from string.
 #[derive(Debug, Clone, Copy)]
 pub enum FromStrRadixErr<E> {
@@ -992,6 +1578,94 @@ where
     }
 }
 
+/// Error when parsing a vector from its display form, i.e. `(x=X, y=Y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromStrErr<E> {
+    /// Thrown when the opening or closing parenthesis is missing.
+    MissingParens,
+    /// Thrown when a missing comma is found.
+    MissingSep,
+    /// A coordinate was labeled with the wrong axis name.
+    BadLabel(Axis),
+    /// A coordinate could not be parsed correctly as an internal error of the
+    /// coordinate type.
+    BadCoord(Axis, E),
+}
+
+impl<E> fmt::Display for FromStrErr<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStrErr::MissingParens => {
+                fmt.write_str("Missing parenthesis around coordinate pair")
+            },
+            FromStrErr::MissingSep => {
+                fmt.write_str("Missing separator comma in coordinate pair")
+            },
+            FromStrErr::BadLabel(axis) => {
+                write!(fmt, "Expected label {} for coordinate", axis)
+            },
+            FromStrErr::BadCoord(axis, err) => {
+                write!(fmt, "Parse error in axis {}: {}", axis, err)
+            },
+        }
+    }
+}
+
+impl<E> Error for FromStrErr<E> where E: Error {}
+
+fn parse_coord<T>(input: &str, axis: Axis) -> Result<T, FromStrErr<T::Err>>
+where
+    T: FromStr,
+{
+    let input = match input.find('=') {
+        Some(index) => {
+            if input[.. index].trim() != axis.to_string() {
+                return Err(FromStrErr::BadLabel(axis));
+            }
+            &input[index + 1 ..]
+        },
+        None => input,
+    };
+    input.trim().parse().map_err(|err| FromStrErr::BadCoord(axis, err))
+}
+
+/// Parses a vector from the same format produced by its [`fmt::Display`]
+/// implementation, i.e. `(x=X, y=Y)`. Labels are optional, so `(X, Y)` is also
+/// accepted.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::coord::Vec2;
+///
+/// # fn main() {
+/// let vector: Vec2<i16> = Vec2 { x: -5, y: 3 };
+/// assert_eq!(vector.to_string().parse(), Ok(vector));
+/// assert_eq!("(-5, 3)".parse(), Ok(vector));
+/// # }
+/// ```
+impl<T> FromStr for Vec2<T>
+where
+    T: FromStr,
+{
+    type Err = FromStrErr<T::Err>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input
+            .trim()
+            .strip_prefix('(')
+            .and_then(|input| input.strip_suffix(')'))
+            .ok_or(FromStrErr::MissingParens)?;
+        let index = input.find(',').ok_or(FromStrErr::MissingSep)?;
+        Ok(Self {
+            x: parse_coord(&input[.. index], Axis::X)?,
+            y: parse_coord(&input[index + 1 ..], Axis::Y)?,
+        })
+    }
+}
+
 impl<T> Unsigned for Vec2<T> where T: Unsigned {}
 
 impl<T> Signed for Vec2<T>
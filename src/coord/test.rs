@@ -1,22 +1,29 @@
 use crate::{
-    coord::Vec2,
+    axis::Axis,
+    coord::{FromStrErr, Orientation, Vec2},
     direc::{DirecVector, Direction},
+    rect::Rect,
 };
 use num::traits::{
     CheckedAdd,
     CheckedDiv,
+    CheckedEuclid,
     CheckedMul,
     CheckedNeg,
     CheckedRem,
     CheckedSub,
+    Euclid,
     SaturatingAdd,
     SaturatingMul,
     SaturatingSub,
+    Signed,
     WrappingAdd,
     WrappingMul,
     WrappingNeg,
     WrappingSub,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::cmp::Ordering;
 
 #[test]
 fn basic_math() {
@@ -341,3 +348,227 @@ fn center_origin() {
     );
     assert_eq!(pair.center_origin(), Vec2 { x: -125, y: -3 });
 }
+
+#[test]
+fn manhattan_dist() {
+    let pair: Vec2<u8> = Vec2 { x: 3, y: 250 };
+    assert_eq!(pair.manhattan_dist(Vec2 { x: 7, y: 245 }), 9);
+    assert_eq!(pair.wrapping_manhattan_dist(&Vec2 { x: 7, y: 245 }), 9);
+    assert_eq!(pair.saturating_manhattan_dist(&Vec2 { x: 7, y: 245 }), 9);
+    assert_eq!(pair.checked_manhattan_dist(&Vec2 { x: 7, y: 245 }), Some(9));
+
+    assert_eq!(pair.wrapping_manhattan_dist(&Vec2 { x: 13, y: 0 }), 4);
+    assert_eq!(pair.saturating_manhattan_dist(&Vec2 { x: 13, y: 0 }), 255);
+    assert_eq!(pair.checked_manhattan_dist(&Vec2 { x: 13, y: 0 }), None);
+
+    let pair: Vec2<i8> = Vec2 { x: -5, y: 100 };
+    assert_eq!(pair.manhattan_dist(Vec2 { x: 5, y: 90 }), 20);
+    assert_eq!(pair.wrapping_manhattan_dist(&Vec2 { x: 5, y: -100 }), -46);
+    assert_eq!(pair.saturating_manhattan_dist(&Vec2 { x: 5, y: -100 }), 127);
+    assert_eq!(pair.checked_manhattan_dist(&Vec2 { x: 5, y: -100 }), None);
+}
+
+#[test]
+fn chebyshev_dist() {
+    let pair: Vec2<u8> = Vec2 { x: 3, y: 250 };
+    assert_eq!(pair.chebyshev_dist(Vec2 { x: 7, y: 245 }), 5);
+    assert_eq!(pair.wrapping_chebyshev_dist(&Vec2 { x: 13, y: 0 }), 250);
+    assert_eq!(pair.saturating_chebyshev_dist(&Vec2 { x: 13, y: 0 }), 250);
+    assert_eq!(pair.checked_chebyshev_dist(&Vec2 { x: 13, y: 0 }), Some(250));
+
+    let pair: Vec2<i8> = Vec2 { x: -5, y: 100 };
+    assert_eq!(pair.chebyshev_dist(Vec2 { x: 5, y: 90 }), 10);
+    assert_eq!(pair.wrapping_chebyshev_dist(&Vec2 { x: 5, y: -100 }), 10);
+    assert_eq!(pair.saturating_chebyshev_dist(&Vec2 { x: 5, y: -100 }), 127);
+    assert_eq!(pair.checked_chebyshev_dist(&Vec2 { x: 5, y: -100 }), None);
+}
+
+#[test]
+fn sqr_euclid_dist() {
+    let pair: Vec2<u8> = Vec2 { x: 3, y: 250 };
+    assert_eq!(pair.sqr_euclid_dist(Vec2 { x: 7, y: 245 }), 41);
+    assert_eq!(pair.wrapping_sqr_euclid_dist(&Vec2 { x: 7, y: 245 }), 41);
+    assert_eq!(pair.saturating_sqr_euclid_dist(&Vec2 { x: 7, y: 245 }), 41);
+    assert_eq!(pair.checked_sqr_euclid_dist(&Vec2 { x: 7, y: 245 }), Some(41));
+
+    assert_eq!(pair.wrapping_sqr_euclid_dist(&Vec2 { x: 19, y: 250 }), 0);
+    assert_eq!(pair.saturating_sqr_euclid_dist(&Vec2 { x: 19, y: 250 }), 255);
+    assert_eq!(pair.checked_sqr_euclid_dist(&Vec2 { x: 19, y: 250 }), None);
+
+    let pair: Vec2<i32> = Vec2 { x: -5, y: 100 };
+    assert_eq!(pair.sqr_euclid_dist(Vec2 { x: 5, y: 90 }), 200);
+}
+
+#[test]
+fn perp_dot() {
+    let pair: Vec2<i8> = Vec2 { x: 3, y: 5 };
+    assert_eq!(pair.perp_dot(Vec2 { x: 2, y: 4 }), 2);
+    assert_eq!(pair.perp_dot_ref(&Vec2 { x: 2, y: 4 }), 2);
+    assert_eq!(pair.perp_dot(Vec2 { x: 6, y: 10 }), 0);
+
+    assert_eq!(pair.wrapping_perp_dot(&Vec2 { x: 2, y: 4 }), 2);
+    assert_eq!(pair.wrapping_perp_dot(&Vec2 { x: -20, y: 40 }), -36);
+
+    assert_eq!(pair.saturating_perp_dot(&Vec2 { x: 2, y: 4 }), 2);
+    assert_eq!(pair.saturating_perp_dot(&Vec2 { x: -20, y: 40 }), 127);
+
+    assert_eq!(pair.checked_perp_dot(&Vec2 { x: 2, y: 4 }), Some(2));
+    assert_eq!(pair.checked_perp_dot(&Vec2 { x: -20, y: 40 }), None);
+}
+
+#[test]
+fn orientation() {
+    let first: Vec2<u8> = Vec2 { x: 1, y: 1 };
+    let second = Vec2 { x: 4, y: 3 };
+    assert_eq!(
+        first.orientation(&second, &Vec2 { x: 2, y: 6 }),
+        Orientation::Clockwise
+    );
+    assert_eq!(
+        first.orientation(&second, &Vec2 { x: 6, y: 0 }),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(
+        first.orientation(&second, &Vec2 { x: 7, y: 5 }),
+        Orientation::Collinear
+    );
+    assert_eq!(first.orientation(&first, &second), Orientation::Collinear);
+
+    let first: Vec2<i32> = Vec2 { x: 0, y: 0 };
+    let second = Vec2 { x: -2, y: 1 };
+    let third = Vec2 { x: 1, y: 3 };
+    assert_eq!(
+        first.orientation(&second, &third),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(first.orientation(&third, &second), Orientation::Clockwise);
+    assert_eq!(
+        second.orientation(&third, &first),
+        Orientation::CounterClockwise
+    );
+}
+
+#[test]
+fn orientation_no_overflow() {
+    let first: Vec2<i32> = Vec2 { x: 0, y: 0 };
+    let second = Vec2 { x: 50000, y: 1 };
+    let third = Vec2 { x: 1, y: 50000 };
+    assert_eq!(first.orientation(&second, &third), Orientation::Clockwise);
+    assert_eq!(
+        first.orientation(&third, &second),
+        Orientation::CounterClockwise
+    );
+
+    let first: Vec2<u16> = Vec2 { x: 0, y: 0 };
+    let second = Vec2 { x: 65535, y: 65533 };
+    let third = Vec2 { x: 65534, y: 65532 };
+    assert_eq!(
+        first.orientation(&second, &third),
+        Orientation::CounterClockwise
+    );
+    let third = Vec2 { x: 65535, y: 65533 };
+    assert_eq!(first.orientation(&second, &third), Orientation::Collinear);
+
+    let mut rng = StdRng::seed_from_u64(3);
+    for _ in 0 .. 2000 {
+        let mut random_point = || Vec2 {
+            x: rng.gen_range(-30000 .. 30000),
+            y: rng.gen_range(-30000 .. 30000),
+        };
+        let points: [Vec2<i32>; 3] =
+            [random_point(), random_point(), random_point()];
+        let wide = points.map(|point| point.map(i64::from));
+        let expected = (wide[1] - wide[0]).perp_dot(wide[2] - wide[0]);
+        let expected = match expected.cmp(&0) {
+            Ordering::Greater => Orientation::Clockwise,
+            Ordering::Less => Orientation::CounterClockwise,
+            Ordering::Equal => Orientation::Collinear,
+        };
+        assert_eq!(points[0].orientation(&points[1], &points[2]), expected);
+    }
+}
+
+#[test]
+fn elemwise_min_max() {
+    let pair: Vec2<i32> = Vec2 { x: 3, y: -5 };
+    assert_eq!(pair.elemwise_min(Vec2 { x: 2, y: 4 }), Vec2 { x: 2, y: -5 });
+    assert_eq!(pair.elemwise_max(Vec2 { x: 2, y: 4 }), Vec2 { x: 3, y: 4 });
+}
+
+#[test]
+fn clamp_to() {
+    let rect: Rect<u8> =
+        Rect { start: Vec2 { x: 2, y: 3 }, size: Vec2 { x: 4, y: 2 } };
+    assert_eq!(Vec2 { x: 0, y: 0 }.clamp_to(&rect), Some(Vec2 { x: 2, y: 3 }));
+    assert_eq!(
+        Vec2 { x: 200, y: 200 }.clamp_to(&rect),
+        Some(Vec2 { x: 5, y: 4 })
+    );
+    assert_eq!(Vec2 { x: 4, y: 9 }.clamp_to(&rect), Some(Vec2 { x: 4, y: 4 }));
+    assert_eq!(Vec2 { x: 3, y: 3 }.clamp_to(&rect), Some(Vec2 { x: 3, y: 3 }));
+
+    let empty: Rect<u8> =
+        Rect { start: Vec2 { x: 2, y: 3 }, size: Vec2 { x: 0, y: 2 } };
+    assert_eq!(Vec2 { x: 3, y: 3 }.clamp_to(&empty), None);
+}
+
+#[test]
+fn abs_signum() {
+    let pair: Vec2<i32> = Vec2 { x: 3, y: -5 };
+    assert_eq!(pair.abs(), Vec2 { x: 3, y: 5 });
+    assert_eq!(pair.signum(), Vec2 { x: 1, y: -1 });
+    assert_eq!(Vec2 { x: 0, y: -7 }.signum(), Vec2 { x: 0, y: -1 });
+}
+
+#[test]
+fn euclid() {
+    let pair: Vec2<i32> = Vec2 { x: -7, y: 7 };
+    let divisor = Vec2 { x: 4, y: -4 };
+    assert_eq!(pair.div_euclid(&divisor), Vec2 { x: -2, y: -1 });
+    assert_eq!(pair.rem_euclid(&divisor), Vec2 { x: 1, y: 3 });
+    assert_eq!(pair.checked_div_euclid(&divisor), Some(Vec2 { x: -2, y: -1 }));
+    assert_eq!(pair.checked_rem_euclid(&Vec2 { x: 4, y: 0 }), None);
+}
+
+#[test]
+fn try_cast() {
+    let pair: Vec2<i32> = Vec2 { x: 200, y: -3 };
+    assert_eq!(pair.try_cast::<i16>(), Ok(Vec2 { x: 200, y: -3 }));
+    assert!(pair.try_cast::<u16>().is_err());
+    assert!(pair.try_cast::<i8>().is_err());
+    assert_eq!(
+        Vec2 { x: 7u64, y: 9 }.try_cast::<u8>(),
+        Ok(Vec2 { x: 7, y: 9 })
+    );
+}
+
+#[test]
+fn cast_lossy() {
+    let pair: Vec2<i32> = Vec2 { x: 200, y: -3 };
+    assert_eq!(pair.cast_lossy::<i16>(), Vec2 { x: 200, y: -3 });
+    assert_eq!(pair.cast_lossy::<u8>(), Vec2 { x: 200, y: 253 });
+    assert_eq!(pair.cast_lossy::<i8>(), Vec2 { x: -56, y: -3 });
+}
+
+#[test]
+fn from_str() {
+    let pair: Vec2<i32> = Vec2 { x: 200, y: -3 };
+    assert_eq!(pair.to_string().parse(), Ok(pair));
+    assert_eq!(" ( x = 200 , y = -3 ) ".parse(), Ok(pair));
+    assert_eq!("(200, -3)".parse(), Ok(pair));
+
+    assert_eq!("200, -3".parse::<Vec2<i32>>(), Err(FromStrErr::MissingParens));
+    assert_eq!("(200; -3)".parse::<Vec2<i32>>(), Err(FromStrErr::MissingSep));
+    assert_eq!(
+        "(y=200, x=-3)".parse::<Vec2<i32>>(),
+        Err(FromStrErr::BadLabel(Axis::X))
+    );
+    assert!(matches!(
+        "(x=200, y=a)".parse::<Vec2<i32>>(),
+        Err(FromStrErr::BadCoord(Axis::Y, _))
+    ));
+    assert!(matches!(
+        "(x=-1, y=3)".parse::<Vec2<u8>>(),
+        Err(FromStrErr::BadCoord(Axis::X, _))
+    ));
+}
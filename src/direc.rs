@@ -1,9 +1,16 @@
 //! Utilities related to directions in the plane.
 
+#[cfg(test)]
+mod test;
+
 use crate::axis::Axis;
 use std::{
+    array,
+    error::Error,
+    fmt,
     ops::{Index, IndexMut, Not},
     slice,
+    str::FromStr,
 };
 
 /// Basic direction in a plane.
@@ -112,6 +119,71 @@ impl Direction {
     }
 }
 
+impl fmt::Display for Direction {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        })
+    }
+}
+
+/// Error when parsing a direction from its name or arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromStrErr {
+    /// The given string is neither the name nor the arrow of a direction.
+    UnknownDirection,
+}
+
+impl fmt::Display for FromStrErr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStrErr::UnknownDirection => {
+                fmt.write_str("Unknown direction name or arrow")
+            },
+        }
+    }
+}
+
+impl Error for FromStrErr {}
+
+/// Parses a direction from its name, as produced by its [`fmt::Display`]
+/// implementation (case-insensitive), or from an arrow: either `↑`, `↓`, `←`,
+/// `→`, or their ASCII versions `^`, `v`, `<`, `>`.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::direc::Direction;
+///
+/// # fn main() {
+/// assert_eq!(Direction::Up.to_string().parse(), Ok(Direction::Up));
+/// assert_eq!("Left".parse(), Ok(Direction::Left));
+/// assert_eq!("→".parse(), Ok(Direction::Right));
+/// assert_eq!("v".parse(), Ok(Direction::Down));
+/// # }
+/// ```
+impl FromStr for Direction {
+    type Err = FromStrErr;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        match input {
+            "↑" | "^" => return Ok(Direction::Up),
+            "↓" | "v" => return Ok(Direction::Down),
+            "←" | "<" => return Ok(Direction::Left),
+            "→" | ">" => return Ok(Direction::Right),
+            _ => (),
+        }
+        Direction::iter()
+            .find(|direction| {
+                input.eq_ignore_ascii_case(&direction.to_string())
+            })
+            .ok_or(FromStrErr::UnknownDirection)
+    }
+}
+
 impl Not for Direction {
     type Output = Direction;
 
@@ -194,6 +266,200 @@ impl<T> DirecMap<T> {
             right: map(Direction::Right),
         }
     }
+
+    /// Maps each entry to a reference.
+    pub fn as_ref(&self) -> DirecMap<&T> {
+        DirecMap {
+            up: &self.up,
+            left: &self.left,
+            down: &self.down,
+            right: &self.right,
+        }
+    }
+
+    /// Maps each entry to a mutable reference.
+    pub fn as_mut(&mut self) -> DirecMap<&mut T> {
+        DirecMap {
+            up: &mut self.up,
+            left: &mut self.left,
+            down: &mut self.down,
+            right: &mut self.right,
+        }
+    }
+
+    /// Iterator over the entries of this mapping, by reference, in the same
+    /// order as [`Direction::iter`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::direc::{DirecMap, Direction};
+    ///
+    /// # fn main() {
+    /// let map = DirecMap { up: 1, left: 2, down: 3, right: 4 };
+    /// let entries: Vec<(Direction, &i32)> = map.iter().collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     vec![
+    ///         (Direction::Up, &1),
+    ///         (Direction::Down, &3),
+    ///         (Direction::Left, &2),
+    ///         (Direction::Right, &4),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn iter(&self) -> MapIter<'_, T> {
+        MapIter { inner: self.as_ref().into_iter() }
+    }
+
+    /// Iterator over the entries of this mapping, by mutable reference, in the
+    /// same order as [`Direction::iter`].
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, T> {
+        MapIterMut { inner: self.as_mut().into_iter() }
+    }
+
+    /// Maps each entry to a given value and builds a new mapping from the
+    /// output of the mapping function.
+    pub fn map<F, U>(self, mut mapper: F) -> DirecMap<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        DirecMap {
+            up: mapper(self.up),
+            left: mapper(self.left),
+            down: mapper(self.down),
+            right: mapper(self.right),
+        }
+    }
+
+    /// Maps each entry to a given value in a new mapping, but the mapping
+    /// function gets the direction of each entry.
+    pub fn map_with_direcs<F, U>(self, mut mapper: F) -> DirecMap<U>
+    where
+        F: FnMut(Direction, T) -> U,
+    {
+        DirecMap {
+            up: mapper(Direction::Up, self.up),
+            left: mapper(Direction::Left, self.left),
+            down: mapper(Direction::Down, self.down),
+            right: mapper(Direction::Right, self.right),
+        }
+    }
+
+    /// Performs a fold/reduce: i.e. accumulates the entries into a final
+    /// result, given an initial value and an accumulator function, in the same
+    /// order as [`Direction::iter`].
+    pub fn fold<F, U>(self, init: U, mut folder: F) -> U
+    where
+        F: FnMut(T, U) -> U,
+    {
+        self.into_iter().fold(init, |acc, (_, elem)| folder(elem, acc))
+    }
+
+    /// Zips two mappings into a mapping of tuples.
+    pub fn zip<U>(self, other: DirecMap<U>) -> DirecMap<(T, U)> {
+        self.zip_with(other, |this, other| (this, other))
+    }
+
+    /// Zips two mappings using a zipper function, which is applied on the
+    /// entries of both mappings with the same direction.
+    pub fn zip_with<F, U, B>(
+        self,
+        other: DirecMap<U>,
+        mut zipper: F,
+    ) -> DirecMap<B>
+    where
+        F: FnMut(T, U) -> B,
+    {
+        DirecMap {
+            up: zipper(self.up, other.up),
+            left: zipper(self.left, other.left),
+            down: zipper(self.down, other.down),
+            right: zipper(self.right, other.right),
+        }
+    }
+
+    /// Rotates the mapping clockwise, i.e. the data associated with a
+    /// direction `d` becomes associated with `d.rotate_clockwise()`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::direc::DirecMap;
+    ///
+    /// # fn main() {
+    /// let map = DirecMap { up: 1, left: 2, down: 3, right: 4 };
+    /// assert_eq!(
+    ///     map.rotate_clockwise(),
+    ///     DirecMap { up: 2, left: 3, down: 4, right: 1 }
+    /// );
+    /// # }
+    /// ```
+    pub fn rotate_clockwise(self) -> Self {
+        Self {
+            up: self.left,
+            left: self.down,
+            down: self.right,
+            right: self.up,
+        }
+    }
+
+    /// Rotates the mapping counter-clockwise, i.e. the data associated with a
+    /// direction `d` becomes associated with `d.rotate_countercw()`.
+    pub fn rotate_countercw(self) -> Self {
+        Self {
+            up: self.right,
+            left: self.up,
+            down: self.left,
+            right: self.down,
+        }
+    }
+}
+
+impl<T> DirecMap<&T> {
+    /// Clones every entry.
+    pub fn cloned(self) -> DirecMap<T>
+    where
+        T: Clone,
+    {
+        self.map(Clone::clone)
+    }
+
+    /// Copies every entry.
+    pub fn copied(self) -> DirecMap<T>
+    where
+        T: Copy,
+    {
+        self.map(|&elem| elem)
+    }
+}
+
+impl<T> DirecMap<Option<T>> {
+    /// Transpose a mapping of options into an option of mappings: a single
+    /// entry with `None` makes the return value be `None`, while all entries
+    /// being `Some` make it be `Some`.
+    pub fn transpose(self) -> Option<DirecMap<T>> {
+        Some(DirecMap {
+            up: self.up?,
+            left: self.left?,
+            down: self.down?,
+            right: self.right?,
+        })
+    }
+}
+
+impl<T, E> DirecMap<Result<T, E>> {
+    /// Transpose a mapping of `Result` into a `Result` of mappings: a single
+    /// entry with `Err` makes the return value be `Err`, while all entries
+    /// being `Ok` make it be `Ok`. Errors get priority in the same order as
+    /// [`Direction::iter`].
+    pub fn transpose_err(self) -> Result<DirecMap<T>, E> {
+        Ok(DirecMap {
+            up: self.up?,
+            down: self.down?,
+            left: self.left?,
+            right: self.right?,
+        })
+    }
 }
 
 impl<T> Index<Direction> for DirecMap<T> {
@@ -219,3 +485,117 @@ impl<T> IndexMut<Direction> for DirecMap<T> {
         }
     }
 }
+
+impl<T> IntoIterator for DirecMap<T> {
+    type Item = (Direction, T);
+    type IntoIter = MapIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let entries = [
+            (Direction::Up, self.up),
+            (Direction::Down, self.down),
+            (Direction::Left, self.left),
+            (Direction::Right, self.right),
+        ];
+        MapIntoIter { inner: IntoIterator::into_iter(entries) }
+    }
+}
+
+impl<'map, T> IntoIterator for &'map DirecMap<T> {
+    type Item = (Direction, &'map T);
+    type IntoIter = MapIter<'map, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'map, T> IntoIterator for &'map mut DirecMap<T> {
+    type Item = (Direction, &'map mut T);
+    type IntoIter = MapIterMut<'map, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over the entries of a [`DirecMap`], by value, in the same order as
+/// [`Direction::iter`].
+#[derive(Debug, Clone)]
+pub struct MapIntoIter<T> {
+    inner: array::IntoIter<(Direction, T), 4>,
+}
+
+impl<T> Iterator for MapIntoIter<T> {
+    type Item = (Direction, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for MapIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for MapIntoIter<T> {}
+
+/// Iterator over the entries of a [`DirecMap`], by reference. See
+/// [`DirecMap::iter`].
+#[derive(Debug, Clone)]
+pub struct MapIter<'map, T> {
+    inner: MapIntoIter<&'map T>,
+}
+
+impl<'map, T> Iterator for MapIter<'map, T> {
+    type Item = (Direction, &'map T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'map, T> DoubleEndedIterator for MapIter<'map, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'map, T> ExactSizeIterator for MapIter<'map, T> {}
+
+/// Iterator over the entries of a [`DirecMap`], by mutable reference. See
+/// [`DirecMap::iter_mut`].
+#[derive(Debug)]
+pub struct MapIterMut<'map, T> {
+    inner: MapIntoIter<&'map mut T>,
+}
+
+impl<'map, T> Iterator for MapIterMut<'map, T> {
+    type Item = (Direction, &'map mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'map, T> DoubleEndedIterator for MapIterMut<'map, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'map, T> ExactSizeIterator for MapIterMut<'map, T> {}
@@ -0,0 +1,144 @@
+use super::{DirecMap, Direction, FromStrErr};
+use crate::axis::Axis;
+
+fn make_map() -> DirecMap<i32> {
+    DirecMap { up: 1, left: 2, down: 3, right: 4 }
+}
+
+#[test]
+fn iter() {
+    let map = make_map();
+    let entries: Vec<_> = map.iter().collect();
+    assert_eq!(
+        entries,
+        vec![
+            (Direction::Up, &1),
+            (Direction::Down, &3),
+            (Direction::Left, &2),
+            (Direction::Right, &4),
+        ]
+    );
+
+    let entries: Vec<_> = map.iter().rev().collect();
+    assert_eq!(
+        entries,
+        vec![
+            (Direction::Right, &4),
+            (Direction::Left, &2),
+            (Direction::Down, &3),
+            (Direction::Up, &1),
+        ]
+    );
+    assert_eq!(map.iter().len(), 4);
+}
+
+#[test]
+fn iter_mut() {
+    let mut map = make_map();
+    for (direction, elem) in &mut map {
+        if direction.axis() == Axis::X {
+            *elem *= 10;
+        }
+    }
+    assert_eq!(map, DirecMap { up: 1, left: 20, down: 3, right: 40 });
+}
+
+#[test]
+fn into_iter() {
+    let entries: Vec<_> = make_map().into_iter().collect();
+    assert_eq!(
+        entries,
+        vec![
+            (Direction::Up, 1),
+            (Direction::Down, 3),
+            (Direction::Left, 2),
+            (Direction::Right, 4),
+        ]
+    );
+}
+
+#[test]
+fn map_and_fold() {
+    let map = make_map();
+    assert_eq!(
+        map.map(|elem| elem * 2),
+        DirecMap { up: 2, left: 4, down: 6, right: 8 }
+    );
+    assert_eq!(
+        map.map_with_direcs(|direction, elem| (direction, elem)),
+        DirecMap::from_direcs(|direction| (direction, map[direction]))
+    );
+    assert_eq!(map.fold(0, |elem, acc| acc * 10 + elem), 1324);
+    assert_eq!(map.as_ref().copied(), map);
+    assert_eq!(map.as_ref().cloned(), map);
+}
+
+#[test]
+fn zip() {
+    let map = make_map();
+    let other = DirecMap { up: 'u', left: 'l', down: 'd', right: 'r' };
+    assert_eq!(
+        map.zip(other),
+        DirecMap {
+            up: (1, 'u'),
+            left: (2, 'l'),
+            down: (3, 'd'),
+            right: (4, 'r')
+        }
+    );
+    assert_eq!(
+        map.zip_with(map, |this, other| this * other),
+        DirecMap { up: 1, left: 4, down: 9, right: 16 }
+    );
+}
+
+#[test]
+fn transpose() {
+    let map = make_map();
+    assert_eq!(map.map(Some).transpose(), Some(map));
+    let mut options = map.map(Some);
+    options.left = None;
+    assert_eq!(options.transpose(), None);
+
+    let results = map.map(Ok::<_, Direction>);
+    assert_eq!(results.transpose_err(), Ok(map));
+    let results = map.map_with_direcs(|direction, elem| {
+        if elem % 2 == 0 {
+            Err(direction)
+        } else {
+            Ok(elem)
+        }
+    });
+    assert_eq!(results.transpose_err(), Err(Direction::Left));
+}
+
+#[test]
+fn rotate() {
+    let map = make_map();
+    let rotated = map.rotate_clockwise();
+    for direction in Direction::iter() {
+        assert_eq!(rotated[direction.rotate_clockwise()], map[direction]);
+    }
+    let rotated = map.rotate_countercw();
+    for direction in Direction::iter() {
+        assert_eq!(rotated[direction.rotate_countercw()], map[direction]);
+    }
+    assert_eq!(map.rotate_clockwise().rotate_countercw(), map);
+}
+
+#[test]
+fn from_str() {
+    for direction in Direction::iter() {
+        assert_eq!(direction.to_string().parse(), Ok(direction));
+    }
+    assert_eq!("DOWN".parse(), Ok(Direction::Down));
+    assert_eq!(" Right ".parse(), Ok(Direction::Right));
+    assert_eq!("↑".parse(), Ok(Direction::Up));
+    assert_eq!("↓".parse(), Ok(Direction::Down));
+    assert_eq!("←".parse(), Ok(Direction::Left));
+    assert_eq!("→".parse(), Ok(Direction::Right));
+    assert_eq!("^".parse(), Ok(Direction::Up));
+    assert_eq!("<".parse(), Ok(Direction::Left));
+    assert_eq!(">".parse(), Ok(Direction::Right));
+    assert_eq!("north".parse::<Direction>(), Err(FromStrErr::UnknownDirection));
+}
@@ -0,0 +1,319 @@
+//! This module provides field of view computation, using symmetric
+//! shadowcasting.
+//!
+//! Symmetric shadowcasting scans the plane in four quadrants (one for each
+//! direction), row by row, keeping track of the slopes of the shadows cast by
+//! opaque points. A transparent point is visible if its center is inside the
+//! lit area of its row, while an opaque point is visible if any part of it is
+//! lit. This makes visibility symmetric: if a transparent point `b` is visible
+//! from a transparent point `a`, then `a` is visible from `b` too. All
+//! computations use integer arithmetic only.
+//!
+//! Points that would not be representable by the coordinate type are treated
+//! as opaque, and they are never visible.
+
+#[cfg(test)]
+mod test;
+
+use crate::{bits::UnsignedOffset, coord::Vec2, direc::Direction, set::Set};
+use num::{FromPrimitive, ToPrimitive};
+
+/// Maximum depth of a scan, so that slope arithmetic does not overflow.
+const MAX_DEPTH: u64 = 1 << 62;
+
+/// Computes the set of points visible from the given origin, given a
+/// predicate that tests whether a point blocks vision. The origin is always
+/// visible. Only points in the disc of the given radius (as in
+/// [`Area::disc`](crate::shape::Area::disc)) are considered, i.e. points whose
+/// distance from the origin is less than `radius + 1/2`.
+///
+/// A point `b` is in the result if, and only if, it is in the disc and
+/// [`line_of_sight`] from `origin` to `b` succeeds.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{coord::Vec2, fov, set::Set};
+///
+/// # fn main() {
+/// // A wall in front of the origin.
+/// let mut walls = Set::new();
+/// walls.insert(Vec2 { x: 4u16, y: 3 });
+/// walls.insert(Vec2 { x: 4, y: 4 });
+/// walls.insert(Vec2 { x: 4, y: 5 });
+///
+/// let origin = Vec2 { x: 3, y: 4 };
+/// let visible = fov::compute(&origin, 3, |point| {
+///     walls.contains(point.as_ref())
+/// });
+///
+/// assert!(visible.contains(Vec2 { x: 3, y: 4 }.as_ref()));
+/// assert!(visible.contains(Vec2 { x: 0, y: 4 }.as_ref()));
+/// assert!(visible.contains(Vec2 { x: 4, y: 4 }.as_ref()));
+/// assert!(!visible.contains(Vec2 { x: 5, y: 4 }.as_ref()));
+/// assert!(!visible.contains(Vec2 { x: 6, y: 4 }.as_ref()));
+/// # }
+/// ```
+pub fn compute<T, D, F>(origin: &Vec2<T>, radius: D, mut is_opaque: F) -> Set<T>
+where
+    T: UnsignedOffset<Output = D> + Ord + Clone,
+    D: ToPrimitive + FromPrimitive,
+    F: FnMut(&Vec2<T>) -> bool,
+{
+    let radius =
+        radius.to_u64().map_or(MAX_DEPTH, |radius| radius.min(MAX_DEPTH));
+    let radius = i128::from(radius);
+    let max_sqr_dist = radius * radius + radius;
+
+    let mut visible = Set::new();
+    visible.insert(origin.clone());
+    for direction in Direction::iter() {
+        let quadrant = Quadrant { origin, direction };
+        let first = Row { depth: 1, start: Slope::LEFT, end: Slope::RIGHT };
+        quadrant.scan(first, radius, &mut is_opaque, |depth, column, point| {
+            if depth * depth + column * column <= max_sqr_dist {
+                visible.insert(point);
+            }
+        });
+    }
+    visible
+}
+
+/// Tests whether `target` is visible from `origin`, given a predicate that
+/// tests whether a point blocks vision. Uses the exact same rules as
+/// [`compute`], without a radius limit. Transparent points see each other
+/// symmetrically, i.e. `line_of_sight(a, b, ..) == line_of_sight(b, a, ..)`
+/// if neither `a` nor `b` is opaque. Only the slopes spanned by `target` are
+/// scanned, so the number of points tested is proportional to the distance.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{coord::Vec2, fov};
+///
+/// # fn main() {
+/// let is_wall = |point: &Vec2<i32>| point.x == 2 && point.y != 0;
+///
+/// let origin = Vec2 { x: 0, y: 0 };
+/// assert!(fov::line_of_sight(&origin, &Vec2 { x: 5, y: 0 }, is_wall));
+/// assert!(fov::line_of_sight(&origin, &Vec2 { x: 2, y: 1 }, is_wall));
+/// assert!(!fov::line_of_sight(&origin, &Vec2 { x: 4, y: 3 }, is_wall));
+/// assert!(!fov::line_of_sight(&origin, &Vec2 { x: 5, y: -4 }, is_wall));
+/// # }
+/// ```
+pub fn line_of_sight<T, D, F>(
+    origin: &Vec2<T>,
+    target: &Vec2<T>,
+    mut is_opaque: F,
+) -> bool
+where
+    T: UnsignedOffset<Output = D> + Ord + Clone,
+    D: ToPrimitive + FromPrimitive,
+    F: FnMut(&Vec2<T>) -> bool,
+{
+    if origin == target {
+        return true;
+    }
+
+    let delta = origin.as_ref().zip_with(target.as_ref(), |origin, target| {
+        let dist = origin.unsigned_distance(target).to_u64()?;
+        if dist > MAX_DEPTH {
+            return None;
+        }
+        let dist = i128::from(dist);
+        Some(if target >= origin { dist } else { -dist })
+    });
+    let delta = match delta.transpose() {
+        Some(delta) => delta,
+        None => return false,
+    };
+
+    // Points on a diagonal belong to two quadrants.
+    Direction::iter().any(|direction| {
+        let target_depth = component(&delta, direction);
+        let target_column = component(&delta, direction.rotate_clockwise());
+        if target_depth <= 0 || target_column.abs() > target_depth {
+            return false;
+        }
+        // Shadows outside of the target's slopes do not affect it.
+        let start = if target_column == -target_depth {
+            Slope::LEFT
+        } else {
+            Slope::of_edge(target_depth, target_column)
+        };
+        let end = if target_column == target_depth {
+            Slope::RIGHT
+        } else {
+            Slope::of_edge(target_depth, target_column + 1)
+        };
+        let first = Row { depth: 1, start, end };
+        let mut found = false;
+        let quadrant = Quadrant { origin, direction };
+        quadrant.scan(
+            first,
+            target_depth,
+            &mut is_opaque,
+            |depth, column, _| {
+                if depth == target_depth && column == target_column {
+                    found = true;
+                }
+            },
+        );
+        found
+    })
+}
+
+/// Component of the given vector in the given direction.
+fn component(vector: &Vec2<i128>, direction: Direction) -> i128 {
+    match direction {
+        Direction::Up => -vector.y,
+        Direction::Down => vector.y,
+        Direction::Left => -vector.x,
+        Direction::Right => vector.x,
+    }
+}
+
+/// A rational slope `numer / denom`, with positive `denom`.
+#[derive(Debug, Clone, Copy)]
+struct Slope {
+    numer: i128,
+    denom: i128,
+}
+
+impl Slope {
+    /// The slope of the left edge of a quadrant.
+    const LEFT: Self = Self { numer: -1, denom: 1 };
+    /// The slope of the right edge of a quadrant.
+    const RIGHT: Self = Self { numer: 1, denom: 1 };
+
+    /// The slope of the left edge of the point in the given column and row.
+    fn of_edge(depth: i128, column: i128) -> Self {
+        Self { numer: 2 * column - 1, denom: 2 * depth }
+    }
+}
+
+/// A row of a quadrant, lit between the start and end slopes.
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    depth: i128,
+    start: Slope,
+    end: Slope,
+}
+
+impl Row {
+    fn next(&self) -> Self {
+        Self { depth: self.depth + 1, ..*self }
+    }
+
+    /// First column of the row, i.e. `depth * start` rounded with ties up.
+    fn min_column(&self) -> i128 {
+        let Slope { numer, denom } = self.start;
+        (2 * self.depth * numer + denom).div_euclid(2 * denom)
+    }
+
+    /// Last column of the row, i.e. `depth * end` rounded with ties down.
+    fn max_column(&self) -> i128 {
+        let Slope { numer, denom } = self.end;
+        -(denom - 2 * self.depth * numer).div_euclid(2 * denom)
+    }
+
+    /// Tests whether the center of the point in the given column is lit.
+    fn is_symmetric(&self, column: i128) -> bool {
+        column * self.start.denom >= self.depth * self.start.numer
+            && column * self.end.denom <= self.depth * self.end.numer
+    }
+}
+
+/// One of the four quadrants around the origin, in the given direction.
+/// Columns grow in the direction rotated clockwise.
+#[derive(Debug, Clone, Copy)]
+struct Quadrant<'origin, T> {
+    origin: &'origin Vec2<T>,
+    direction: Direction,
+}
+
+impl<'origin, T, D> Quadrant<'origin, T>
+where
+    T: UnsignedOffset<Output = D> + Ord + Clone,
+    D: FromPrimitive,
+{
+    /// Converts a depth and a column into a point, if representable.
+    fn point(&self, depth: i128, column: i128) -> Option<Vec2<T>> {
+        let side = if column >= 0 {
+            self.direction.rotate_clockwise()
+        } else {
+            self.direction.rotate_countercw()
+        };
+        let point = self.origin.clone();
+        let point = move_by(point, self.direction, depth)?;
+        move_by(point, side, column.abs())
+    }
+
+    /// Scans rows from the given first row up to the given depth, calling
+    /// `reveal` for each visible point with its depth and column.
+    fn scan<F, R>(
+        &self,
+        first: Row,
+        max_depth: i128,
+        is_opaque: &mut F,
+        mut reveal: R,
+    ) where
+        F: FnMut(&Vec2<T>) -> bool,
+        R: FnMut(i128, i128, Vec2<T>),
+    {
+        let mut rows = vec![first];
+        while let Some(mut row) = rows.pop() {
+            if row.depth > max_depth {
+                continue;
+            }
+            let mut prev_opaque = None;
+            for column in row.min_column() ..= row.max_column() {
+                let point = self.point(row.depth, column);
+                let opaque = match &point {
+                    Some(point) => is_opaque(point),
+                    None => true,
+                };
+                if let Some(point) = point {
+                    if opaque || row.is_symmetric(column) {
+                        reveal(row.depth, column, point);
+                    }
+                }
+                match (prev_opaque, opaque) {
+                    (Some(true), false) => {
+                        row.start = Slope::of_edge(row.depth, column);
+                    },
+                    (Some(false), true) => {
+                        let mut next = row.next();
+                        next.end = Slope::of_edge(row.depth, column);
+                        rows.push(next);
+                    },
+                    _ => (),
+                }
+                prev_opaque = Some(opaque);
+            }
+            if prev_opaque == Some(false) {
+                rows.push(row.next());
+            }
+        }
+    }
+}
+
+/// Moves a point in the given direction by a non-negative amount, if
+/// representable.
+fn move_by<T, D>(
+    point: Vec2<T>,
+    direction: Direction,
+    amount: i128,
+) -> Option<Vec2<T>>
+where
+    T: UnsignedOffset<Output = D>,
+    D: FromPrimitive,
+{
+    let amount = D::from_i128(amount)?;
+    let Vec2 { x, y } = point;
+    let point = match direction {
+        Direction::Up => Vec2 { x, y: y.checked_sub_offset(&amount)? },
+        Direction::Down => Vec2 { x, y: y.checked_add_offset(&amount)? },
+        Direction::Left => Vec2 { x: x.checked_sub_offset(&amount)?, y },
+        Direction::Right => Vec2 { x: x.checked_add_offset(&amount)?, y },
+    };
+    Some(point)
+}
@@ -0,0 +1,151 @@
+use super::{compute, line_of_sight};
+use crate::{coord::Vec2, set::Set, shape::Area};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SIZE: i32 = 12;
+
+/// A random map of walls, with everything outside of the square `[0, SIZE)`
+/// being a wall too.
+fn random_walls(seed: u64) -> impl Fn(&Vec2<i32>) -> bool {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut walls = Set::new();
+    for y in 0 .. SIZE {
+        for x in 0 .. SIZE {
+            if rng.gen_bool(0.25) {
+                walls.insert(Vec2 { x, y });
+            }
+        }
+    }
+    move |point| {
+        point.x < 0
+            || point.y < 0
+            || point.x >= SIZE
+            || point.y >= SIZE
+            || walls.contains(point.as_ref())
+    }
+}
+
+fn square() -> impl Iterator<Item = Vec2<i32>> {
+    (0 .. SIZE).flat_map(|y| (0 .. SIZE).map(move |x| Vec2 { x, y }))
+}
+
+#[test]
+fn open_field() {
+    for radius in 0 .. 10 {
+        let center = Vec2 { x: -20i32, y: 35 };
+        let visible = compute(&center, radius, |_| false);
+        let disc: Set<_> = Area::disc(center, radius).collect();
+        assert_eq!(visible, disc);
+    }
+
+    let origin = Vec2 { x: 1u8, y: 254 };
+    let visible = compute(&origin, 4, |_| false);
+    let disc: Set<_> = Area::disc(origin, 4).collect();
+    assert_eq!(visible, disc);
+}
+
+#[test]
+fn pillar() {
+    let origin = Vec2 { x: 0, y: 0 };
+    let is_pillar = |point: &Vec2<i32>| *point == Vec2 { x: 2, y: 0 };
+    let visible = compute(&origin, 6, is_pillar);
+    assert!(visible.contains(Vec2 { x: 1, y: 0 }.as_ref()));
+    assert!(visible.contains(Vec2 { x: 2, y: 0 }.as_ref()));
+    assert!(!visible.contains(Vec2 { x: 3, y: 0 }.as_ref()));
+    assert!(!visible.contains(Vec2 { x: 6, y: 0 }.as_ref()));
+    assert!(!visible.contains(Vec2 { x: 6, y: 1 }.as_ref()));
+    assert!(visible.contains(Vec2 { x: 6, y: 2 }.as_ref()));
+    assert!(visible.contains(Vec2 { x: 3, y: 1 }.as_ref()));
+    assert!(visible.contains(Vec2 { x: -6, y: 0 }.as_ref()));
+}
+
+#[test]
+fn symmetric() {
+    for seed in 0 .. 4 {
+        let is_wall = random_walls(seed);
+        for first in square().filter(|point| !is_wall(point)) {
+            for second in square().filter(|point| !is_wall(point)) {
+                assert_eq!(
+                    line_of_sight(&first, &second, &is_wall),
+                    line_of_sight(&second, &first, &is_wall),
+                    "seed {}, {} and {}",
+                    seed,
+                    first,
+                    second
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn compute_agrees_with_line_of_sight() {
+    for seed in 4 .. 8 {
+        let is_wall = random_walls(seed);
+        for origin in square().filter(|point| !is_wall(point)).step_by(7) {
+            for radius in [3u32, 5, 20] {
+                let visible = compute(&origin, radius, &is_wall);
+                let disc: Set<_> = Area::disc(origin, radius).collect();
+                for point in square() {
+                    assert_eq!(
+                        visible.contains(point.as_ref()),
+                        disc.contains(point.as_ref())
+                            && line_of_sight(&origin, &point, &is_wall),
+                        "seed {}, from {} to {}",
+                        seed,
+                        origin,
+                        point
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn unrepresentable_points() {
+    let origin = Vec2 { x: i8::MAX, y: i8::MIN };
+    let target = Vec2 { x: i8::MIN, y: i8::MAX };
+    assert!(line_of_sight(&origin, &target, |_| false));
+    assert!(!line_of_sight(&origin, &target, |point| point.x == 0));
+
+    let visible = compute(&origin, u8::MAX, |_| false);
+    assert!(visible.contains(Vec2 { x: i8::MIN, y: i8::MIN }.as_ref()));
+}
+
+#[test]
+fn line_of_sight_matches_compute_everywhere() {
+    for seed in 8 .. 40 {
+        let is_wall = random_walls(seed);
+        let origin = Vec2 { x: (seed % 12) as i32, y: (seed / 3 % 12) as i32 };
+        let visible = compute(&origin, 2 * SIZE as u32, &is_wall);
+        for point in square() {
+            assert_eq!(
+                visible.contains(point.as_ref()),
+                line_of_sight(&origin, &point, &is_wall),
+                "seed {}, from {} to {}",
+                seed,
+                origin,
+                point
+            );
+        }
+    }
+}
+
+#[test]
+fn line_of_sight_is_linear() {
+    let origin = Vec2 { x: 0i64, y: 0 };
+    for target in [
+        Vec2 { x: 1000, y: 377 },
+        Vec2 { x: -1000, y: 1000 },
+        Vec2 { x: 3, y: -1000 },
+    ] {
+        let mut calls = 0;
+        let visible = line_of_sight(&origin, &target, |_| {
+            calls += 1;
+            false
+        });
+        assert!(visible);
+        assert!(calls <= 4 * 1000, "{} calls to {}", calls, target);
+    }
+}
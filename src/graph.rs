@@ -5,31 +5,64 @@ mod test;
 
 use crate::{
     axis::{self, Axis},
-    bits::Distance,
     coord::Vec2,
     direc::{DirecMap, DirecVector, Direction},
     map::{Map, Rows},
+    path::Path,
+    segment::Segment,
 };
 use num::{CheckedAdd, CheckedSub, One, Zero};
 use std::{
     borrow::Borrow,
-    cmp::Ordering,
-    collections::{BTreeSet, BinaryHeap, HashMap},
-    hash::{Hash, Hasher},
+    cmp::{Ordering, Reverse},
+    collections::{
+        hash_map::RandomState,
+        BTreeMap,
+        BTreeSet,
+        BinaryHeap,
+        HashMap,
+        HashSet,
+    },
+    hash::{BuildHasher, Hash, Hasher},
     iter::Peekable,
-    ops::AddAssign,
+    ops::{Add, AddAssign, Bound, Mul, Sub},
 };
 
 /// The vertices_edges of a vertex. More specifically, at which direction the
 /// vertex is connected?
 pub type VertexEdges = DirecMap<bool>;
 
+/// How a graph deals with crossing edges, i.e. edges whose middles cross each
+/// other. See [`Graph::with_planarity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Planarity {
+    /// Edges may freely cross each other. This is the default.
+    #[default]
+    Free,
+    /// Edges may not cross each other. Connecting two vertices fails if the
+    /// new edge would cross another edge, and paths made by
+    /// [`Graph::make_path`] do not cross existing edges. Paths may still pass
+    /// through existing vertices, or join an existing edge, run along it and
+    /// leave it, splitting it where they join or leave it (T-junctions).
+    Forbid,
+    /// Edges may not cross each other, but crossing points become new vertices
+    /// (junctions) when connecting with [`Graph::connect_with_junctions`],
+    /// splitting both edges. [`Graph::connect`] still fails on crossings.
+    /// Paths made by [`Graph::make_path`] may cross existing edges,
+    /// besides everything allowed by [`Planarity::Forbid`].
+    Junction,
+}
+
 /// A simple graph of points in a plane. Being simple means two points can only
 /// be connected once with each other or not connected at all (with each other),
 /// no pair of points can be connected with each other more than once. Also,
 /// graphs might not be necessarily planar, although they can (this means two
 /// edges can overlap). Points can only be connected in "straight" 2D
-/// directions.
+/// directions. See [`Graph::crossings`] for finding where edges overlap.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "impl-serde",
@@ -44,6 +77,8 @@ where
         serde(bound(deserialize = "T: serde::Deserialize<'de> + Clone"))
     )]
     vertices_edges: Map<T, VertexEdges>,
+    #[cfg_attr(feature = "impl-serde", serde(default))]
+    planarity: Planarity,
 }
 
 impl<T> Default for Graph<T>
@@ -61,7 +96,64 @@ where
 {
     /// Creates a new empty graph.
     pub fn new() -> Self {
-        Self { vertices_edges: Map::new() }
+        Self::with_planarity(Planarity::Free)
+    }
+
+    /// Creates a new empty graph which deals with crossing edges as given by
+    /// `planarity`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     graph::{Graph, Planarity},
+    /// };
+    ///
+    /// # fn main() {
+    /// let left = Vec2 { x: 0u16, y: 2 };
+    /// let right = Vec2 { x: 4, y: 2 };
+    /// let top = Vec2 { x: 2, y: 0 };
+    /// let bottom = Vec2 { x: 2, y: 4 };
+    /// let vertices = vec![left, right, top, bottom];
+    ///
+    /// let mut graph = Graph::with_planarity(Planarity::Forbid);
+    /// graph.extend_vertices(vertices.clone());
+    /// assert!(graph.connect(left.as_ref(), right.as_ref()));
+    /// assert!(!graph.connect(top.as_ref(), bottom.as_ref()));
+    /// assert!(!graph.are_connected(top.as_ref(), bottom.as_ref()));
+    ///
+    /// let mut graph = Graph::with_planarity(Planarity::Junction);
+    /// graph.extend_vertices(vertices);
+    /// assert!(graph.connect(left.as_ref(), right.as_ref()));
+    /// assert!(!graph.connect(top.as_ref(), bottom.as_ref()));
+    /// assert!(graph.connect_with_junctions(top.as_ref(), bottom.as_ref()));
+    /// let junction = Vec2 { x: 2, y: 2 };
+    /// assert!(graph.are_connected(left.as_ref(), junction.as_ref()));
+    /// assert!(graph.are_connected(junction.as_ref(), right.as_ref()));
+    /// assert!(graph.are_connected(top.as_ref(), junction.as_ref()));
+    /// assert!(graph.are_connected(junction.as_ref(), bottom.as_ref()));
+    /// assert!(graph.is_planar_embedding());
+    /// # }
+    /// ```
+    pub fn with_planarity(planarity: Planarity) -> Self {
+        Self { vertices_edges: Map::new(), planarity }
+    }
+
+    /// How this graph deals with crossing edges.
+    pub fn planarity(&self) -> Planarity {
+        self.planarity
+    }
+
+    /// Changes how this graph deals with crossing edges. Returns if it was
+    /// really changed, since it cannot forbid crossings if there already are
+    /// crossing edges (see [`Graph::is_planar_embedding`]).
+    pub fn set_planarity(&mut self, planarity: Planarity) -> bool {
+        if planarity != Planarity::Free && !self.is_planar_embedding() {
+            false
+        } else {
+            self.planarity = planarity;
+            true
+        }
     }
 
     /// Creates the graph from a list of vertices (and no vertices_edges!).
@@ -75,6 +167,7 @@ where
                 .into_iter()
                 .map(|vertex| (vertex, DirecMap::from_direcs(|_| false)))
                 .collect(),
+            planarity: Planarity::Free,
         }
     }
 
@@ -110,7 +203,7 @@ where
     }
 
     /// Extends the graph edge list from a list of vertices-pair connected in
-    /// vertices_edges.
+    /// vertices_edges. Each pair is connected as in [`Graph::connect`].
     pub fn extend_edges<'vertex, U, I>(&mut self, vertices_edges: I)
     where
         U: 'vertex + Ord,
@@ -183,7 +276,8 @@ where
 
     /// Creates a new vertex in the graph (without creating vertices_edges!).
     /// Returns if the vertex was really created (i.e. vertex not already
-    /// there).
+    /// there). If the vertex is in the middle of an edge, the edge is split, as
+    /// in [`Graph::split_edge_at`].
     pub fn create_vertex(&mut self, vertex: Vec2<T>) -> bool
     where
         T: Clone,
@@ -191,8 +285,10 @@ where
         let mut vertices_edges = DirecMap::from_direcs(|_| false);
 
         for direction in Direction::iter() {
-            if let Some(neighbour) =
-                self.vertices_edges.first_neighbour(vertex.as_ref(), direction)
+            if let Some((neighbour, _)) = self
+                .vertices_edges
+                .neighbours_from(vertex.as_ref(), direction)
+                .next()
             {
                 let neighbour_edges =
                     self.vertex_edges(neighbour).expect("Inconsistent graph");
@@ -206,8 +302,71 @@ where
     }
 
     /// Connects the given two vertices and returns if they were really
-    /// connected (i.e. they were previously disconnected).
+    /// connected (i.e. they were previously disconnected). Unless crossings
+    /// are allowed (see [`Planarity`]), the vertices are not connected if the
+    /// new edge would cross other edges; see [`Graph::connect_with_junctions`]
+    /// for creating junctions at the crossing points instead.
     pub fn connect<U>(&mut self, vertex_a: Vec2<&U>, vertex_b: Vec2<&U>) -> bool
+    where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        let direction = match self.connection_direction(vertex_a, vertex_b) {
+            Some(direction) => direction,
+            None => return false,
+        };
+        if self.planarity != Planarity::Free
+            && !self.crossing_points(vertex_a, vertex_b).is_empty()
+        {
+            return false;
+        }
+        self.link(vertex_a, vertex_b, direction);
+        true
+    }
+
+    /// Connects the given two vertices as in [`Graph::connect`], except that,
+    /// when crossings become junctions (see [`Planarity::Junction`]), junction
+    /// vertices are created at the points where the new edge would cross other
+    /// edges, splitting both edges.
+    pub fn connect_with_junctions<U>(
+        &mut self,
+        vertex_a: Vec2<&U>,
+        vertex_b: Vec2<&U>,
+    ) -> bool
+    where
+        U: Ord,
+        T: Borrow<U> + Clone,
+    {
+        if self.planarity != Planarity::Junction {
+            return self.connect(vertex_a, vertex_b);
+        }
+        let direction = match self.connection_direction(vertex_a, vertex_b) {
+            Some(direction) => direction,
+            None => return false,
+        };
+
+        let junctions: Vec<Vec2<T>> = self
+            .crossing_points(vertex_a, vertex_b)
+            .into_iter()
+            .map(Vec2::cloned)
+            .collect();
+        let mut previous = vertex_a;
+        for junction in &junctions {
+            self.create_vertex(junction.clone());
+            self.link(previous, junction.borrow(), direction);
+            previous = junction.borrow();
+        }
+        self.link(previous, vertex_b, direction);
+        true
+    }
+
+    /// The direction from the first vertex to the second one, if they are not
+    /// connected yet. Panics if they are not neighbours.
+    fn connection_direction<U>(
+        &self,
+        vertex_a: Vec2<&U>,
+        vertex_b: Vec2<&U>,
+    ) -> Option<Direction>
     where
         U: Ord,
         T: Borrow<U>,
@@ -215,28 +374,93 @@ where
         let direction =
             vertex_a.direction_to(&vertex_b).expect("no straight direction");
 
-        let first_neighbour = self
-            .vertices_edges
-            .first_neighbour(vertex_a, direction)
-            .map(|neighbour| neighbour.map(Borrow::borrow));
+        let first_neighbour =
+            self.vertices_edges.first_neighbour(vertex_a, direction);
 
-        if first_neighbour != Some(vertex_b) {
+        if first_neighbour.map(|neighbour| neighbour.map(Borrow::borrow))
+            != Some(vertex_b)
+        {
             panic!("Vertices are not neighbours")
         }
 
-        let mut vertices_edges =
+        let vertices_edges =
             self.vertex_edges(vertex_a).expect("Invalid vertex");
         if vertices_edges[direction] {
-            false
+            None
         } else {
-            vertices_edges[direction] = true;
-            let _ = self.vertices_edges.update(vertex_a, vertices_edges);
-            let mut vertices_edges =
-                self.vertex_edges(vertex_b).expect("Invalid vertex");
-            vertices_edges[!direction] = true;
-            let _ = self.vertices_edges.update(vertex_b, vertices_edges);
-            true
+            Some(direction)
+        }
+    }
+
+    /// Sets the edge flags between two neighbour vertices, given the direction
+    /// from the first one to the second one.
+    fn link<U>(
+        &mut self,
+        vertex_a: Vec2<&U>,
+        vertex_b: Vec2<&U>,
+        direction: Direction,
+    ) where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        let mut vertices_edges =
+            self.vertex_edges(vertex_a).expect("Invalid vertex");
+        vertices_edges[direction] = true;
+        let _ = self.vertices_edges.update(vertex_a, vertices_edges);
+        let mut vertices_edges =
+            self.vertex_edges(vertex_b).expect("Invalid vertex");
+        vertices_edges[!direction] = true;
+        let _ = self.vertices_edges.update(vertex_b, vertices_edges);
+    }
+
+    /// Points where an edge between the given two neighbour vertices would
+    /// cross existing edges, sorted from `vertex_a` to `vertex_b`.
+    fn crossing_points<U>(
+        &self,
+        vertex_a: Vec2<&U>,
+        vertex_b: Vec2<&U>,
+    ) -> Vec<Vec2<&T>>
+    where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        let direction =
+            vertex_a.direction_to(&vertex_b).expect("no straight direction");
+        let axis = direction.axis();
+        let (low, high) = if vertex_a[axis] < vertex_b[axis] {
+            (vertex_a[axis], vertex_b[axis])
+        } else {
+            (vertex_b[axis], vertex_a[axis])
+        };
+        let fixed = vertex_a[!axis];
+        let line = self
+            .vertices_edges
+            .first_neighbour(vertex_a, direction)
+            .expect("Invalid vertex")[!axis];
+
+        let mut points: Vec<_> = self
+            .connections()
+            .filter(|(start, end)| {
+                let start = start.map(Borrow::<U>::borrow);
+                let end = end.map(Borrow::<U>::borrow);
+                start[axis] == end[axis]
+                    && low < start[axis]
+                    && start[axis] < high
+                    && start[!axis] < fixed
+                    && fixed < end[!axis]
+            })
+            .map(|(start, _)| {
+                let mut point = start;
+                point[!axis] = line;
+                point
+            })
+            .collect();
+
+        points.sort_by(|first, second| first[axis].cmp(second[axis]));
+        if vertex_b[axis] < vertex_a[axis] {
+            points.reverse();
         }
+        points
     }
 
     /// Disconnects the given two vertices and returns if they were really
@@ -287,10 +511,153 @@ where
         }
     }
 
+    /// Iterator over the edges of this graph as segments. Each segment starts
+    /// at the smallest vertex of its edge, i.e. it points right or down, and it
+    /// is yielded in the same order as [`Graph::connections`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u16>::new();
+    /// graph.create_vertex(Vec2 { x: 2, y: 3 });
+    /// graph.create_vertex(Vec2 { x: 2, y: 7 });
+    /// graph.create_vertex(Vec2 { x: 0, y: 7 });
+    /// graph.connect(Vec2 { x: 2, y: 3 }.as_ref(), Vec2 { x: 2, y: 7 }.as_ref());
+    /// graph.connect(Vec2 { x: 2, y: 7 }.as_ref(), Vec2 { x: 0, y: 7 }.as_ref());
+    ///
+    /// let segments: Vec<_> = graph.segments().collect();
+    /// assert_eq!(
+    ///     segments,
+    ///     vec![
+    ///         Segment::new(
+    ///             Vec2 { x: 2, y: 3 },
+    ///             DirecVector { direction: Direction::Down, magnitude: 4 },
+    ///         ),
+    ///         Segment::new(
+    ///             Vec2 { x: 0, y: 7 },
+    ///             DirecVector { direction: Direction::Right, magnitude: 2 },
+    ///         ),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn segments(&self) -> Segments<'_, T> {
+        Segments { connections: self.connections() }
+    }
+
+    /// Lists every pair of crossing edges, together with the point where they
+    /// cross. Edges meeting at a shared vertex do not cross. Crossings are
+    /// found by sweeping a vertical line over the plane, from left to right,
+    /// and they are listed in this order (and then from top to bottom).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, graph::Graph};
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<i32>::new();
+    /// let left = Vec2 { x: -2, y: 0 };
+    /// let right = Vec2 { x: 2, y: 0 };
+    /// let top = Vec2 { x: 0, y: -3 };
+    /// let bottom = Vec2 { x: 0, y: 3 };
+    /// for &vertex in &[left, right, top, bottom] {
+    ///     graph.create_vertex(vertex);
+    /// }
+    /// graph.connect(left.as_ref(), right.as_ref());
+    /// graph.connect(top.as_ref(), bottom.as_ref());
+    ///
+    /// let crossings = graph.crossings();
+    /// assert_eq!(crossings.len(), 1);
+    /// assert_eq!(crossings[0].horizontal, (left.as_ref(), right.as_ref()));
+    /// assert_eq!(crossings[0].vertical, (top.as_ref(), bottom.as_ref()));
+    /// assert_eq!(crossings[0].point, Vec2 { x: &0, y: &0 });
+    /// assert!(!graph.is_planar_embedding());
+    /// # }
+    /// ```
+    pub fn crossings(&self) -> Vec<Crossing<'_, T>> {
+        let mut crossings = Vec::new();
+        self.sweep_crossings(|crossing| {
+            crossings.push(crossing);
+            true
+        });
+        crossings
+    }
+
+    /// Tests whether no pair of edges cross, i.e. whether the graph, as
+    /// drawn in the plane, is a planar embedding. See [`Graph::crossings`].
+    pub fn is_planar_embedding(&self) -> bool {
+        self.sweep_crossings(|_| false)
+    }
+
+    /// Sweeps a vertical line over the plane, calling `visit` for each
+    /// crossing, until `visit` returns `false`. Returns whether the sweep
+    /// finished without being stopped.
+    fn sweep_crossings<'graph, F>(&'graph self, mut visit: F) -> bool
+    where
+        F: FnMut(Crossing<'graph, T>) -> bool,
+    {
+        let mut events = Vec::new();
+        for (vertex, neighbour) in self.connections() {
+            if vertex.y == neighbour.y {
+                events.push((vertex.x, SweepEvent::Enter, vertex, neighbour));
+                events.push((
+                    neighbour.x,
+                    SweepEvent::Leave,
+                    vertex,
+                    neighbour,
+                ));
+            } else {
+                events.push((vertex.x, SweepEvent::Cross, vertex, neighbour));
+            }
+        }
+        events.sort_by(|first, second| {
+            (first.0, first.1, first.2.y).cmp(&(second.0, second.1, second.2.y))
+        });
+
+        // Horizontal edges strictly crossing the sweep line, by their Y
+        // coordinate. No two of them share a line.
+        let mut active = BTreeMap::new();
+        for (_, event, start, end) in events {
+            match event {
+                SweepEvent::Leave => {
+                    active.remove(start.y);
+                },
+                SweepEvent::Cross => {
+                    let range =
+                        (Bound::Excluded(start.y), Bound::Excluded(end.y));
+                    for (_, &horizontal) in active.range::<T, _>(range) {
+                        let crossing = Crossing {
+                            horizontal,
+                            vertical: (start, end),
+                            point: Vec2 { x: start.x, y: horizontal.0.y },
+                        };
+                        if !visit(crossing) {
+                            return false;
+                        }
+                    }
+                },
+                SweepEvent::Enter => {
+                    active.insert(start.y, (start, end));
+                },
+            }
+        }
+
+        true
+    }
+
     /// Removes a vertex but attempts to connect vertices_edges between its
     /// neighbours, if the target vertex had vertices_edges in both
     /// directions. Returns if the vertex was really removed (i.e. it was in
-    /// the graph).
+    /// the graph). If crossings are not allowed (see [`Planarity`]), a vertex
+    /// with edges in all directions is not removed, since its edges would
+    /// cross.
     pub fn remove_vertex<U>(&mut self, vertex: Vec2<&U>) -> bool
     where
         U: Ord,
@@ -301,6 +668,12 @@ where
             None => return false,
         };
 
+        if self.planarity != Planarity::Free
+            && Direction::iter().all(|direction| vertices_edges[direction])
+        {
+            return false;
+        }
+
         for direction in Direction::iter() {
             if let Some((neighbour, neighbour_edges)) = self
                 .vertices_edges
@@ -355,6 +728,232 @@ where
         true
     }
 
+    /// Finds the edge passing through the given point, if the point is not a
+    /// vertex but it is in the middle of an edge. The edge is given by its two
+    /// vertices, the smallest one first. If a vertical and a horizontal edge
+    /// cross at the point, the vertical one is returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, graph::Graph};
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<i32>::new();
+    /// let top = Vec2 { x: 3, y: -5 };
+    /// let bottom = Vec2 { x: 3, y: 5 };
+    /// graph.create_vertex(top);
+    /// graph.create_vertex(bottom);
+    /// graph.connect(top.as_ref(), bottom.as_ref());
+    ///
+    /// assert_eq!(
+    ///     graph.edge_at(Vec2 { x: 3, y: 1 }.as_ref()),
+    ///     Some((top.as_ref(), bottom.as_ref()))
+    /// );
+    /// assert_eq!(graph.edge_at(top.as_ref()), None);
+    /// assert_eq!(graph.edge_at(Vec2 { x: 4, y: 1 }.as_ref()), None);
+    /// # }
+    /// ```
+    pub fn edge_at<U>(&self, point: Vec2<&U>) -> Option<(Vec2<&T>, Vec2<&T>)>
+    where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        if self.vertices_edges.contains(point) {
+            return None;
+        }
+        Axis::iter().find_map(|axis| self.edge_along(point, axis))
+    }
+
+    /// Finds the edge in the given axis passing through the given point,
+    /// assuming the point is not a vertex.
+    fn edge_along<U>(
+        &self,
+        point: Vec2<&U>,
+        axis: Axis,
+    ) -> Option<(Vec2<&T>, Vec2<&T>)>
+    where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        let direction = Direction::from_axis_neg(axis);
+        let (start, edges) =
+            self.vertices_edges.neighbours_from(point, direction).next()?;
+        if edges[!direction] {
+            let (end, _) = self
+                .vertices_edges
+                .neighbours_from(point, !direction)
+                .next()
+                .expect("Inconsistent graph");
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Splits the edge passing through the given point, creating a vertex at
+    /// the point and connecting it to both ends of the edge. If two edges
+    /// cross at the point, both are split. Returns if an edge was really
+    /// split (i.e. the point was in the middle of an edge).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, graph::Graph};
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u8>::new();
+    /// let left = Vec2 { x: 1, y: 4 };
+    /// let right = Vec2 { x: 9, y: 4 };
+    /// graph.create_vertex(left);
+    /// graph.create_vertex(right);
+    /// graph.connect(left.as_ref(), right.as_ref());
+    ///
+    /// let middle = Vec2 { x: 6, y: 4 };
+    /// assert!(graph.split_edge_at(middle));
+    /// assert!(graph.are_connected(left.as_ref(), middle.as_ref()));
+    /// assert!(graph.are_connected(middle.as_ref(), right.as_ref()));
+    /// assert!(!graph.split_edge_at(middle));
+    /// assert!(!graph.split_edge_at(Vec2 { x: 6, y: 5 }));
+    /// # }
+    /// ```
+    pub fn split_edge_at(&mut self, point: Vec2<T>) -> bool
+    where
+        T: Clone,
+    {
+        if self.edge_at::<T>(point.as_ref()).is_none() {
+            return false;
+        }
+        self.create_vertex(point)
+    }
+
+    /// Tests whether the given vertex is redundant, i.e. it is just a straight
+    /// pass-through: it has edges only on opposite sides, in the same axis.
+    /// Removing a redundant vertex merges its two edges into one, without
+    /// changing which points the graph connects.
+    pub fn is_redundant<U>(&self, vertex: Vec2<&U>) -> bool
+    where
+        U: Ord,
+        T: Borrow<U>,
+    {
+        match self.vertex_edges(vertex) {
+            Some(edges) => Axis::iter().any(|axis| {
+                Direction::iter().all(|direction| {
+                    edges[direction] == (direction.axis() == axis)
+                })
+            }),
+            None => false,
+        }
+    }
+
+    /// Removes every redundant vertex (see [`Graph::is_redundant`]), merging
+    /// their edges. Returns how many vertices were removed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, graph::Graph};
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u8>::new();
+    /// let vertices = [
+    ///     Vec2 { x: 0, y: 0 },
+    ///     Vec2 { x: 3, y: 0 },
+    ///     Vec2 { x: 5, y: 0 },
+    ///     Vec2 { x: 5, y: 4 },
+    /// ];
+    /// graph.extend_vertices(vertices.iter().copied());
+    /// for pair in vertices.windows(2) {
+    ///     graph.connect(pair[0].as_ref(), pair[1].as_ref());
+    /// }
+    ///
+    /// assert!(graph.is_redundant(vertices[1].as_ref()));
+    /// assert!(!graph.is_redundant(vertices[2].as_ref()));
+    /// assert_eq!(graph.simplify(), 1);
+    /// assert!(graph.are_connected(vertices[0].as_ref(), vertices[2].as_ref()));
+    /// assert!(graph.are_connected(vertices[2].as_ref(), vertices[3].as_ref()));
+    /// assert_eq!(graph.vertices_edges().len(), 3);
+    /// # }
+    /// ```
+    pub fn simplify(&mut self) -> usize
+    where
+        T: Clone,
+    {
+        let redundant: Vec<_> = self
+            .vertices_edges
+            .rows()
+            .map(|(vertex, _)| vertex)
+            .filter(|vertex| self.is_redundant(*vertex))
+            .map(Vec2::cloned)
+            .collect();
+
+        for vertex in &redundant {
+            self.remove_vertex::<T>(vertex.as_ref());
+        }
+
+        redundant.len()
+    }
+
+    /// Inserts vertices along every edge, at a regular spacing of `step` from
+    /// the smallest vertex of the edge, splitting the edge. This is the
+    /// inverse of [`Graph::simplify`]. Points where another edge crosses the
+    /// edge are skipped, so crossing edges are not joined. Nothing is done if
+    /// `step` is not positive.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, graph::Graph};
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u8>::new();
+    /// let start = Vec2 { x: 2, y: 1 };
+    /// let end = Vec2 { x: 2, y: 8 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(end);
+    /// graph.connect(start.as_ref(), end.as_ref());
+    ///
+    /// graph.densify(3);
+    /// let vertices: Vec<_> =
+    ///     graph.vertices_edges().rows().map(|(vertex, _)| vertex).collect();
+    /// assert_eq!(
+    ///     vertices,
+    ///     vec![
+    ///         Vec2 { x: &2, y: &1 },
+    ///         Vec2 { x: &2, y: &4 },
+    ///         Vec2 { x: &2, y: &7 },
+    ///         Vec2 { x: &2, y: &8 },
+    ///     ]
+    /// );
+    ///
+    /// assert_eq!(graph.simplify(), 2);
+    /// assert!(graph.are_connected(start.as_ref(), end.as_ref()));
+    /// # }
+    /// ```
+    pub fn densify(&mut self, step: T)
+    where
+        T: Clone + CheckedAdd + Zero,
+    {
+        if step <= T::zero() {
+            return;
+        }
+
+        let edges: Vec<_> = self
+            .connections()
+            .map(|(vertex, neighbour)| (vertex.cloned(), neighbour.cloned()))
+            .collect();
+
+        for (start, end) in edges {
+            let axis = if start.x == end.x { Axis::Y } else { Axis::X };
+            let mut point = start;
+            while let Some(coord) = point[axis].checked_add(&step) {
+                if coord >= end[axis] {
+                    break;
+                }
+                point[axis] = coord;
+                if self.edge_along::<T>(point.as_ref(), !axis).is_none() {
+                    self.create_vertex(point.clone());
+                }
+            }
+        }
+    }
+
     /// Creates iterator over connected components of the graph. E.g. each
     /// "island" in the graph makes a new subgraph yielded by the iterator.
     pub fn components(&self) -> Components<T> {
@@ -370,7 +969,15 @@ where
     /// a "penalty" added to the cost of paths when they turn. Recomended values
     /// for "penalty" are `0`, `1` or `2`. For minimizing turns, `2` is
     /// strongly recommended. The only points actually used are the ones
-    /// validated by the given function `valid_points`.
+    /// validated by the given function `valid_points`. The start and the goal
+    /// become vertices if they are not already. Wherever the path starts,
+    /// ends or turns in the middle of an existing edge, the edge is split,
+    /// forming a T-junction (see [`Graph::split_edge_at`]). If the graph does
+    /// not allow crossing edges, the path does not cross existing edges, as
+    /// described by [`Planarity`]. This is the same as [`Graph::find_path`]
+    /// followed by [`Graph::commit_path`]. The search is plain A*; a faster
+    /// jump point search can be selected for uniform grids through
+    /// [`PathMakerBuf::with_options`].
     ///
     /// # Examples
     ///
@@ -498,29 +1105,647 @@ where
     {
         PathMakerBuf::new().make_path(self, start, goal, penalty, valid_points)
     }
-}
 
-/// A buffer for an A* search algorithm useful for saving a few deallocations
-/// and allocations when performing lots of searches. See [`Graph::make_path`].
-#[derive(Debug, Clone)]
-pub struct PathMakerBuf<T>
-where
-    T: Clone + Hash + Ord,
-    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
-{
-    predecessors: HashMap<Vec2<T>, Vec2<T>>,
-    travelled: HashMap<Vec2<T>, Cost<T>>,
-    cost_points: BinaryHeap<BinaryHeapEntry<T>>,
-}
-
-impl<T> Default for PathMakerBuf<T>
-where
-    T: Clone + Hash + Ord,
-    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
-{
-    fn default() -> Self {
-        Self::new()
-    }
+    /// Plans a path from the given starting point till the "goal" point,
+    /// exactly as [`Graph::make_path`] would, but without changing the graph.
+    /// The cost of the path is its length plus `penalty` for each turn. The
+    /// path can be applied later with [`Graph::commit_path`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u16>::new();
+    /// let start = Vec2 { x: 1, y: 1 };
+    /// let goal = Vec2 { x: 4, y: 5 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(goal);
+    ///
+    /// let valid_points = |point: &Vec2<u16>| point.x == 1 || point.y == 5;
+    /// let path = graph.find_path(&start, &goal, &2, valid_points).unwrap();
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![
+    ///         DirecVector { direction: Direction::Down, magnitude: 4 },
+    ///         DirecVector { direction: Direction::Right, magnitude: 3 },
+    ///     ]
+    /// );
+    /// assert_eq!(path.length(), 7);
+    /// assert_eq!(path.turns(), 1);
+    /// assert_eq!(path.cost, 9);
+    /// assert!(!graph.are_connected(start.as_ref(), goal.as_ref()));
+    ///
+    /// let mut expected = graph.clone();
+    /// expected.commit_path(&path.start, &path.steps);
+    /// graph.make_path(&start, &goal, &2, valid_points);
+    /// assert_eq!(graph, expected);
+    /// # }
+    /// ```
+    pub fn find_path<'points, F>(
+        &self,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone + Hash,
+        T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+        T: AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        PathMakerBuf::new().find_path(self, start, goal, penalty, valid_points)
+    }
+
+    /// Makes a path from the given starting point till the "goal" point, as
+    /// [`Graph::make_path`] does, but each step has its own cost. The cost of
+    /// a step is given by the function `step_cost`, which receives the point
+    /// before the step, the point after it, and the direction of the step.
+    /// Steps with cost `None` are impassable. The path with the smallest total
+    /// cost (steps plus `penalty` for each turn) is chosen. `min_step_cost`
+    /// must not be greater than the cost of any step, since it keeps the
+    /// search heuristics admissible; otherwise the path might not be the
+    /// cheapest one. If every step costs one, this is the same as
+    /// [`Graph::make_path`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u32>::new();
+    /// let start = Vec2 { x: 0, y: 1 };
+    /// let goal = Vec2 { x: 4, y: 1 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(goal);
+    ///
+    /// // A swamp lies between the start and the goal.
+    /// let is_swamp = |point: &Vec2<u32>| point.y == 1 && point.x % 4 != 0;
+    /// let steps = graph.make_path_weighted(
+    ///     &start,
+    ///     &goal,
+    ///     &0,
+    ///     &1,
+    ///     |_, to, _| {
+    ///         if to.x > 4 || to.y > 1 {
+    ///             None
+    ///         } else if is_swamp(to) {
+    ///             Some(5)
+    ///         } else {
+    ///             Some(1)
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(
+    ///     steps,
+    ///     Some(vec![
+    ///         DirecVector { direction: Direction::Up, magnitude: 1 },
+    ///         DirecVector { direction: Direction::Right, magnitude: 4 },
+    ///         DirecVector { direction: Direction::Down, magnitude: 1 },
+    ///     ])
+    /// );
+    /// # }
+    /// ```
+    pub fn make_path_weighted<'points, F>(
+        &mut self,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        min_step_cost: &'points T,
+        step_cost: F,
+    ) -> Option<Vec<DirecVector<T>>>
+    where
+        T: Clone + Hash + Mul<Output = T>,
+        T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+        T: AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    {
+        PathMakerBuf::new().make_path_weighted(
+            self,
+            start,
+            goal,
+            penalty,
+            min_step_cost,
+            step_cost,
+        )
+    }
+
+    /// Plans a path with weighted steps, exactly as
+    /// [`Graph::make_path_weighted`] would, but without changing the graph.
+    /// The cost of the path is the cost of its steps plus `penalty` for each
+    /// turn. See also [`Graph::find_path`].
+    pub fn find_path_weighted<'points, F>(
+        &self,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        min_step_cost: &'points T,
+        step_cost: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone + Hash + Mul<Output = T>,
+        T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+        T: AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    {
+        PathMakerBuf::new().find_path_weighted(
+            self,
+            start,
+            goal,
+            penalty,
+            min_step_cost,
+            step_cost,
+        )
+    }
+
+    /// Makes a path from the closest of the given starting points till the
+    /// closest of the given "goal" points, as [`Graph::make_path`] does. The
+    /// search heuristics is the distance to the closest goal. The start and
+    /// the end of the resulting path tell which start and which goal were
+    /// used. Returns `None` if there are no starts, no goals, or no path.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<i32>::new();
+    /// let spawns = [Vec2 { x: 0, y: 0 }, Vec2 { x: 0, y: 10 }];
+    /// let exits = [Vec2 { x: 9, y: 0 }, Vec2 { x: 4, y: 10 }];
+    /// graph.extend_vertices(spawns.iter().chain(&exits).copied());
+    ///
+    /// let path = graph
+    ///     .make_path_multi(&spawns, &exits, &2, |point| {
+    ///         (0 ..= 10).contains(&point.x) && (0 ..= 10).contains(&point.y)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(path.start, spawns[1]);
+    /// assert_eq!(path.end(), exits[1]);
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![DirecVector { direction: Direction::Right, magnitude: 4 }]
+    /// );
+    /// assert!(graph.are_connected(path.start.as_ref(), exits[1].as_ref()));
+    /// # }
+    /// ```
+    pub fn make_path_multi<'points, I, J, F>(
+        &mut self,
+        starts: I,
+        goals: J,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone + Hash,
+        T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+        T: AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        J: IntoIterator<Item = &'points Vec2<T>>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        PathMakerBuf::new().make_path_multi(
+            self,
+            starts,
+            goals,
+            penalty,
+            valid_points,
+        )
+    }
+
+    /// Makes a path from the closest of the given starting points till the
+    /// closest point accepted by `is_goal`, as [`Graph::make_path`] does. Since
+    /// goals are not known in advance, no search heuristics is used (it is a
+    /// Dijkstra search). The start and the end of the resulting path tell
+    /// which start and which goal were used. Returns `None` if there are no
+    /// starts or no path.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u8>::new();
+    /// let start = Vec2 { x: 4, y: 4 };
+    /// graph.create_vertex(start);
+    ///
+    /// // Path to the closest border of the map.
+    /// let path = graph
+    ///     .make_path_to(
+    ///         Some(&start),
+    ///         |point| point.x == 0 || point.y == 9 || point.x == 9,
+    ///         &2,
+    ///         |point| point.x <= 9 && point.y <= 9,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(path.end(), Vec2 { x: 0, y: 4 });
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![DirecVector { direction: Direction::Left, magnitude: 4 }]
+    /// );
+    /// # }
+    /// ```
+    pub fn make_path_to<'points, I, G, F>(
+        &mut self,
+        starts: I,
+        is_goal: G,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone + Hash,
+        T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+        T: AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        G: FnMut(&Vec2<T>) -> bool,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        PathMakerBuf::new().make_path_to(
+            self,
+            starts,
+            is_goal,
+            penalty,
+            valid_points,
+        )
+    }
+
+    /// Applies a path previously planned by [`Graph::find_path`], starting at
+    /// the given point, creating vertices where the path starts, ends and
+    /// turns, and connecting them. Vertices already in the graph along the
+    /// path are connected too, and edges are split where the path starts,
+    /// ends or turns in the middle of them. Returns whether the path was
+    /// committed: if the graph forbids crossing edges (see
+    /// [`Planarity::Forbid`]) and the path would cross an existing edge or
+    /// itself, the graph is left unchanged and `false` is returned. Paths found
+    /// by [`Graph::find_path`] in the same graph never cross existing edges.
+    pub fn commit_path(
+        &mut self,
+        start: &Vec2<T>,
+        steps: &[DirecVector<T>],
+    ) -> bool
+    where
+        T: Clone + Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    {
+        if self.planarity == Planarity::Forbid
+            && self.path_crosses(start, steps)
+        {
+            return false;
+        }
+
+        self.create_vertex(start.clone());
+        let mut last_vertex = start.clone();
+        let mut current = start.clone();
+
+        for step in steps {
+            let mut moved = T::zero();
+            while moved < step.magnitude {
+                current = current.move_one(step.direction);
+                moved += T::one();
+                if moved == step.magnitude {
+                    self.create_vertex(current.clone());
+                }
+                if self.vertices_edges.contains::<T>(current.as_ref()) {
+                    self.connect_with_junctions::<T>(
+                        last_vertex.as_ref(),
+                        current.as_ref(),
+                    );
+                    last_vertex = current.clone();
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Tests whether committing the given path would make edges cross, i.e.
+    /// whether the path passes straight through the middle of an existing
+    /// edge, or through the middle of one of its own steps.
+    fn path_crosses(&self, start: &Vec2<T>, steps: &[DirecVector<T>]) -> bool
+    where
+        T: Clone + Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    {
+        let mut vertices = BTreeSet::new();
+        vertices.insert(start.clone());
+        // Points the path passes straight through, by the axis of the step.
+        let mut passes = Vec2 { x: BTreeSet::new(), y: BTreeSet::new() };
+        let mut current = start.clone();
+
+        for step in steps {
+            let mut moved = T::zero();
+            while moved < step.magnitude {
+                current = current.move_one(step.direction);
+                moved += T::one();
+                if moved == step.magnitude {
+                    vertices.insert(current.clone());
+                } else {
+                    passes[step.direction.axis()].insert(current.clone());
+                }
+            }
+        }
+
+        Axis::iter().any(|axis| {
+            passes[axis].iter().any(|point| {
+                !vertices.contains(point)
+                    && !self.vertices_edges.contains::<T>(point.as_ref())
+                    && (passes[!axis].contains(point)
+                        || self
+                            .edge_along::<T>(point.as_ref(), !axis)
+                            .is_some())
+            })
+        })
+    }
+
+    /// Finds the shortest route from the given start vertex till the "goal"
+    /// vertex along the existing edges of the graph, using the A* search
+    /// algorithm. The length of an edge is the distance between its vertices,
+    /// and `penalty` is added to the cost of routes whenever they turn, as in
+    /// [`Graph::make_path`] (`0` disables penalties). Among routes of the same
+    /// cost, the one with fewer turns is chosen. Returns `None` if either point
+    /// is not a vertex, or if there is no route.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::Graph,
+    /// };
+    ///
+    /// # fn main() {
+    /// // A loop with a shortcut in the middle:
+    /// // a---b---c
+    /// // |   |   |
+    /// // d---e---f
+    /// let mut graph = Graph::<u32>::new();
+    /// let a = Vec2 { x: 0, y: 0 };
+    /// let b = Vec2 { x: 4, y: 0 };
+    /// let c = Vec2 { x: 8, y: 0 };
+    /// let d = Vec2 { x: 0, y: 3 };
+    /// let e = Vec2 { x: 4, y: 3 };
+    /// let f = Vec2 { x: 8, y: 3 };
+    /// graph.extend_vertices(vec![a, b, c, d, e, f]);
+    /// for &(first, second) in
+    ///     &[(a, b), (b, c), (a, d), (b, e), (c, f), (d, e), (e, f)]
+    /// {
+    ///     graph.connect(first.as_ref(), second.as_ref());
+    /// }
+    ///
+    /// let route = graph.shortest_route(&a, &f, &0).unwrap();
+    /// assert_eq!(route.vertices.first(), Some(&a));
+    /// assert_eq!(route.vertices.last(), Some(&f));
+    /// let length: u32 = route.steps.iter().map(|step| step.magnitude).sum();
+    /// assert_eq!(length, 11);
+    ///
+    /// // Turn penalties make the route avoid the shortcut.
+    /// let route = graph.shortest_route(&a, &f, &2).unwrap();
+    /// assert_eq!(route.vertices, vec![a, b, c, f]);
+    /// assert_eq!(
+    ///     route.steps,
+    ///     vec![
+    ///         DirecVector { direction: Direction::Right, magnitude: 8 },
+    ///         DirecVector { direction: Direction::Down, magnitude: 3 },
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn shortest_route(
+        &self,
+        start: &Vec2<T>,
+        goal: &Vec2<T>,
+        penalty: &T,
+    ) -> Option<Route<T>>
+    where
+        T: Clone + Hash + Zero + One + Add<Output = T> + Sub<Output = T>,
+    {
+        if !self.vertices_edges.contains::<T>(start.as_ref())
+            || !self.vertices_edges.contains::<T>(goal.as_ref())
+        {
+            return None;
+        }
+
+        // States are vertices together with the direction they were reached
+        // from, so that turns are accounted correctly.
+        let start_state = (start.as_ref(), None);
+        let mut travelled = HashMap::<_, Cost<T>>::new();
+        let mut predecessors = HashMap::new();
+        let mut cost_states = BinaryHeap::new();
+        travelled.insert(start_state, Cost::new());
+        cost_states.push(Reverse((Cost::new(), Cost::new(), start_state)));
+
+        let goal_state = loop {
+            let Reverse((_, cost, state)) = cost_states.pop()?;
+            let (vertex, arrival) = state;
+            if matches!(travelled.get(&state), Some(best) if *best < cost) {
+                continue;
+            }
+            if vertex == goal.as_ref() {
+                break state;
+            }
+
+            for direction in Direction::iter() {
+                let neighbour = match self.connected_at::<T>(vertex, direction)
+                {
+                    Some(neighbour) => neighbour,
+                    None => continue,
+                };
+                let mut attempt = cost.clone();
+                attempt.distance = attempt.distance
+                    + vertex.cloned().manhattan_dist(neighbour.cloned());
+                if matches!(arrival, Some(prev) if prev != direction) {
+                    attempt.turns = attempt.turns + T::one();
+                    attempt.distance = attempt.distance + penalty.clone();
+                }
+
+                let next_state = (neighbour, Some(direction));
+                let improves = match travelled.get(&next_state) {
+                    Some(best) => attempt < *best,
+                    None => true,
+                };
+                if improves {
+                    travelled.insert(next_state, attempt.clone());
+                    predecessors.insert(next_state, state);
+                    let mut estimate = attempt.clone();
+                    estimate.distance = estimate.distance
+                        + neighbour.cloned().manhattan_dist(goal.clone());
+                    cost_states.push(Reverse((estimate, attempt, next_state)));
+                }
+            }
+        };
+
+        let mut vertices = vec![goal.clone()];
+        let mut steps = Vec::<DirecVector<T>>::new();
+        let mut state = goal_state;
+        while let (vertex, Some(direction)) = state {
+            let prev = predecessors[&state];
+            let magnitude = prev.0.cloned().manhattan_dist(vertex.cloned());
+            match steps.last_mut() {
+                Some(step) if step.direction == direction => {
+                    step.magnitude = step.magnitude.clone() + magnitude
+                },
+                _ => steps.push(DirecVector { direction, magnitude }),
+            }
+            vertices.push(prev.0.cloned());
+            state = prev;
+        }
+
+        vertices.reverse();
+        steps.reverse();
+        Some(Route { vertices, steps })
+    }
+}
+
+/// A route along the edges of a graph. See [`Graph::shortest_route`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Route<T> {
+    /// The vertices in the route, from the start till the goal.
+    pub vertices: Vec<Vec2<T>>,
+    /// The steps of the route. Straight runs through vertices are merged into
+    /// a single step.
+    pub steps: Vec<DirecVector<T>>,
+}
+
+/// The algorithm used by the searches of a [`PathMakerBuf`]. See
+/// [`SearchOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum SearchStrategy {
+    /// Plain A* search, which expands every neighbour of a point. This is the
+    /// default.
+    #[default]
+    AStar,
+    /// Jump point search, a variant of A* for grids where every step costs
+    /// the same. Instead of expanding every neighbour of a point, it jumps in
+    /// straight lines till a point where the path might need to turn, which
+    /// expands far fewer points on open maps. Paths found have the same
+    /// length as the ones found by A*, but they may turn at other points.
+    JumpPoint,
+}
+
+/// Options for the searches of a [`PathMakerBuf`]. See
+/// [`PathMakerBuf::with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct SearchOptions {
+    /// The search algorithm. Jump point search is only used when every step
+    /// costs one, there is no penalty for turning and the graph allows
+    /// crossing edges; A* is used otherwise, e.g. for weighted steps.
+    pub strategy: SearchStrategy,
+    /// Maximum number of points a single jump may move through. Jumps over
+    /// open regions only stop at obstacles, so this should be set if
+    /// `valid_points` accepts unbounded regions. `None` means no limit.
+    pub max_jump: Option<usize>,
+}
+
+/// A buffer for an A* search algorithm useful for saving a few deallocations
+/// and allocations when performing lots of searches. The buffer also holds
+/// the options of its searches. See [`Graph::make_path`] and
+/// [`Graph::make_path_weighted`].
+///
+/// Searches are deterministic: among points with the same estimated cost,
+/// the point whose path has fewer turns is expanded first, then the point
+/// with the smallest heuristics (i.e. the closest one to the goal), and then
+/// the smallest point in the order of [`Vec2`] (`y`, then `x`). When crossings
+/// are forbidden, points in the middle of an edge are searched once for each
+/// axis they are reached along, since this changes where paths may go on
+/// (see [`Planarity::Forbid`]), vertical arrivals first. Neighbours
+/// are visited in the order of [`Direction::iter`], and an equally cheap way
+/// of reaching a point does not replace the first one found. Therefore, the
+/// same search always finds the same path, in any run and on any platform,
+/// whatever the hasher `S` of the buffer is, as long as the arithmetic of `T`
+/// is deterministic.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     coord::Vec2,
+///     direc::{DirecVector, Direction},
+///     graph::{Graph, PathMakerBuf},
+/// };
+/// use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+///
+/// # fn main() {
+/// let graph = Graph::<i32>::new();
+/// let start = Vec2 { x: 0, y: 0 };
+/// let goal = Vec2 { x: 3, y: 3 };
+/// let valid_points =
+///     |point: &Vec2<i32>| (0 ..= 3).contains(&point.x) && point.y >= 0;
+///
+/// // A fixed hasher, instead of a randomly seeded one.
+/// let mut buf = PathMakerBuf::with_hasher(
+///     BuildHasherDefault::<DefaultHasher>::default(),
+/// );
+/// let path = buf.find_path(&graph, &start, &goal, &0, valid_points).unwrap();
+/// // Every path with a single turn is as short, but this is always the one.
+/// assert_eq!(
+///     path.steps,
+///     vec![
+///         DirecVector { direction: Direction::Right, magnitude: 3 },
+///         DirecVector { direction: Direction::Down, magnitude: 3 },
+///     ]
+/// );
+/// let other_path =
+///     PathMakerBuf::new().find_path(&graph, &start, &goal, &0, valid_points);
+/// assert_eq!(other_path, Some(path));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathMakerBuf<T, S = RandomState>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    S: BuildHasher,
+{
+    predecessors: HashMap<SearchState<T>, SearchState<T>, S>,
+    travelled: HashMap<SearchState<T>, Cost<T>, S>,
+    cost_points: BinaryHeap<BinaryHeapEntry<T>>,
+    closest: Option<(T, Cost<T>, SearchState<T>)>,
+    options: SearchOptions,
+    expansions: usize,
+}
+
+impl<T, S> Default for PathMakerBuf<T, S>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self {
+            predecessors: HashMap::default(),
+            travelled: HashMap::default(),
+            cost_points: BinaryHeap::new(),
+            closest: None,
+            options: SearchOptions::default(),
+            expansions: 0,
+        }
+    }
 }
 
 impl<T> PathMakerBuf<T>
@@ -530,175 +1755,902 @@ where
 {
     /// Creates a new empty path maker buffer.
     pub fn new() -> Self {
+        Self::with_options(SearchOptions::default())
+    }
+
+    /// Creates a new empty path maker buffer whose searches use the given
+    /// options.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     graph::{Graph, PathMakerBuf, SearchOptions, SearchStrategy},
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u8>::new();
+    /// let start = Vec2 { x: 0, y: 0 };
+    /// let goal = Vec2 { x: 30, y: 20 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(goal);
+    /// let valid_points = |point: &Vec2<u8>| point.x <= 40 && point.y <= 40;
+    ///
+    /// let mut a_star = PathMakerBuf::new();
+    /// let expected =
+    ///     a_star.find_path(&graph, &start, &goal, &0, valid_points).unwrap();
+    ///
+    /// let mut jumping = PathMakerBuf::with_options(SearchOptions {
+    ///     strategy: SearchStrategy::JumpPoint,
+    ///     max_jump: None,
+    /// });
+    /// let path =
+    ///     jumping.find_path(&graph, &start, &goal, &0, valid_points).unwrap();
+    /// assert_eq!(path.length(), expected.length());
+    /// assert!(jumping.expansions() < a_star.expansions());
+    /// # }
+    /// ```
+    pub fn with_options(options: SearchOptions) -> Self {
+        Self { options, ..Self::default() }
+    }
+}
+
+impl<T, S> PathMakerBuf<T, S>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    S: BuildHasher,
+{
+    /// Creates a new empty path maker buffer which uses the given hasher
+    /// builder for its hash maps. The hasher does not change the paths found,
+    /// only the speed of the searches.
+    pub fn with_hasher(hasher: S) -> Self
+    where
+        S: Clone,
+    {
         Self {
-            predecessors: HashMap::new(),
-            travelled: HashMap::new(),
+            predecessors: HashMap::with_hasher(hasher.clone()),
+            travelled: HashMap::with_hasher(hasher),
             cost_points: BinaryHeap::new(),
+            closest: None,
+            options: SearchOptions::default(),
+            expansions: 0,
+        }
+    }
+
+    /// The options of the searches using this buffer.
+    pub fn options(&self) -> SearchOptions {
+        self.options
+    }
+
+    /// Changes the options of the next searches using this buffer.
+    pub fn set_options(&mut self, options: SearchOptions) {
+        self.options = options;
+    }
+
+    /// How many points the last search using this buffer expanded. Searches
+    /// started by [`PathMakerBuf::path_search`] count their expansions in
+    /// [`PathSearch::expansions`] instead.
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    /// Performs the A* search algorithm using this buffer. See
+    /// [`Graph::make_path`].
+    pub fn make_path<'graph, 'points, F>(
+        &mut self,
+        graph: &'graph mut Graph<T>,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Vec<DirecVector<T>>>
+    where
+        T: Clone,
+        T: Zero + One + AddAssign + AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let path = self.find_path(graph, start, goal, penalty, valid_points)?;
+        graph.commit_path(start, &path.steps);
+        Some(path.steps)
+    }
+
+    /// Performs the A* search algorithm using this buffer, without changing
+    /// the graph. See [`Graph::find_path`].
+    pub fn find_path<'graph, 'points, F>(
+        &mut self,
+        graph: &'graph Graph<T>,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone,
+        T: Zero + One + AddAssign + AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        self.search_unit(
+            graph,
+            Some(start),
+            |point| point == goal,
+            penalty,
+            valid_points,
+            |point| point.clone().manhattan_dist(goal.clone()),
+        )
+    }
+
+    /// Performs the A* search algorithm with weighted steps using this
+    /// buffer. See [`Graph::make_path_weighted`].
+    pub fn make_path_weighted<'graph, 'points, F>(
+        &mut self,
+        graph: &'graph mut Graph<T>,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        min_step_cost: &'points T,
+        step_cost: F,
+    ) -> Option<Vec<DirecVector<T>>>
+    where
+        T: Clone + Mul<Output = T>,
+        T: Zero + One + AddAssign + AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    {
+        let path = self.find_path_weighted(
+            graph,
+            start,
+            goal,
+            penalty,
+            min_step_cost,
+            step_cost,
+        )?;
+        graph.commit_path(start, &path.steps);
+        Some(path.steps)
+    }
+
+    /// Performs the A* search algorithm with weighted steps using this
+    /// buffer, without changing the graph. See [`Graph::find_path_weighted`].
+    pub fn find_path_weighted<'graph, 'points, F>(
+        &mut self,
+        graph: &'graph Graph<T>,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        min_step_cost: &'points T,
+        step_cost: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone + Mul<Output = T>,
+        T: Zero + One + AddAssign + AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    {
+        self.search(
+            graph,
+            Some(start),
+            |point| point == goal,
+            penalty,
+            step_cost,
+            |point| {
+                point.clone().manhattan_dist(goal.clone())
+                    * min_step_cost.clone()
+            },
+        )
+    }
+
+    /// Performs the A* search algorithm from many starts to many goals using
+    /// this buffer. See [`Graph::make_path_multi`].
+    pub fn make_path_multi<'graph, 'points, I, J, F>(
+        &mut self,
+        graph: &'graph mut Graph<T>,
+        starts: I,
+        goals: J,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        T: Clone,
+        T: Zero + One + AddAssign + AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        J: IntoIterator<Item = &'points Vec2<T>>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let goals: HashSet<_> = goals.into_iter().collect();
+        if goals.is_empty() {
+            return None;
         }
+        let path = self.search_unit(
+            graph,
+            starts,
+            |point| goals.contains(point),
+            penalty,
+            valid_points,
+            |point| {
+                goals
+                    .iter()
+                    .map(|&goal| point.clone().manhattan_dist(goal.clone()))
+                    .min()
+                    .unwrap_or_else(T::zero)
+            },
+        )?;
+        graph.commit_path(&path.start, &path.steps);
+        Some(path)
     }
 
-    /// Performs the A* search algorithm using this buffer. See
-    /// [`Graph::make_path`].
-    pub fn make_path<'graph, 'points, F>(
+    /// Performs a search from many starts to the closest point accepted by
+    /// the given predicate using this buffer. See [`Graph::make_path_to`].
+    pub fn make_path_to<'graph, 'points, I, G, F>(
         &mut self,
         graph: &'graph mut Graph<T>,
-        start: &'points Vec2<T>,
-        goal: &'points Vec2<T>,
+        starts: I,
+        is_goal: G,
         penalty: &'points T,
         valid_points: F,
-    ) -> Option<Vec<DirecVector<T>>>
+    ) -> Option<Path<T>>
     where
         T: Clone,
         T: Zero + One + AddAssign + AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        G: FnMut(&Vec2<T>) -> bool,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let path = self.search_unit(
+            graph,
+            starts,
+            is_goal,
+            penalty,
+            valid_points,
+            |_| T::zero(),
+        )?;
+        graph.commit_path(&path.start, &path.steps);
+        Some(path)
+    }
+
+    /// Runs the A* search algorithm with the given goal test, step cost
+    /// function and heuristic function, and clears this buffer afterwards.
+    fn search<'graph, 'points, I, G, C, H>(
+        &mut self,
+        graph: &'graph Graph<T>,
+        starts: I,
+        is_goal: G,
+        penalty: &'points T,
+        step_cost: C,
+        heuristic: H,
+    ) -> Option<Path<T>>
+    where
+        T: AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        G: FnMut(&Vec2<T>) -> bool,
+        C: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+        H: FnMut(&Vec2<T>) -> T,
+    {
+        self.clear();
+        self.push_starts(starts);
+        self.expansions = 0;
+        let mut call = PathMakerCall::new(
+            self, graph, is_goal, penalty, step_cost, heuristic,
+        );
+        let path = call.run();
+        self.clear();
+        path
+    }
+
+    /// Runs a search where every step into a valid point costs one, with the
+    /// given goal test and heuristic function, and clears this buffer
+    /// afterwards. Jump point search is used if the options of this buffer
+    /// select it and it applies to the search.
+    fn search_unit<'graph, 'points, I, G, F, H>(
+        &mut self,
+        graph: &'graph Graph<T>,
+        starts: I,
+        is_goal: G,
+        penalty: &'points T,
+        valid_points: F,
+        heuristic: H,
+    ) -> Option<Path<T>>
+    where
+        T: AddAssign<&'points T>,
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        G: FnMut(&Vec2<T>) -> bool,
         F: FnMut(&Vec2<T>) -> bool,
+        H: FnMut(&Vec2<T>) -> T,
     {
-        let mut call =
-            PathMakerCall::new(self, graph, start, goal, penalty, valid_points);
+        let jumping = self.options.strategy == SearchStrategy::JumpPoint
+            && penalty.is_zero()
+            && graph.planarity != Planarity::Forbid;
+        if !jumping {
+            return self.search(
+                graph,
+                starts,
+                is_goal,
+                penalty,
+                unit_steps(valid_points),
+                heuristic,
+            );
+        }
+
+        self.clear();
+        self.push_starts(starts);
+        self.expansions = 0;
+        let mut call = PathMakerCall::new(
+            self,
+            graph,
+            is_goal,
+            penalty,
+            unit_steps(valid_points),
+            heuristic,
+        );
+        call.jumping = true;
         let path = call.run();
+        self.clear();
+        path
+    }
+
+    /// Starts an incremental A* search using this buffer, which can be run a
+    /// few steps at a time, spreading a long search over many frames. The
+    /// search does not change the graph; see [`Graph::find_path`]. The
+    /// search state lives in this buffer until the search is dropped.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::{Graph, PathMakerBuf, SearchStatus},
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<i32>::new();
+    /// let start = Vec2 { x: 0, y: 0 };
+    /// let goal = Vec2 { x: 20, y: 0 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(goal);
+    ///
+    /// let mut buf = PathMakerBuf::new();
+    /// let mut search =
+    ///     buf.path_search(&graph, &start, &goal, &2, |point| point.y == 0);
+    /// assert_eq!(search.step(5), SearchStatus::Pending);
+    /// assert_eq!(search.expansions(), 5);
+    /// let path = loop {
+    ///     match search.step(5) {
+    ///         SearchStatus::Pending => (),
+    ///         SearchStatus::Found(path) => break path,
+    ///         SearchStatus::NoPath => panic!("there is a path"),
+    ///     }
+    /// };
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![DirecVector { direction: Direction::Right, magnitude: 20 }]
+    /// );
+    /// assert_eq!(path.cost, 20);
+    ///
+    /// graph.commit_path(&path.start, &path.steps);
+    /// assert!(graph.are_connected(start.as_ref(), goal.as_ref()));
+    /// # }
+    /// ```
+    pub fn path_search<'buf, 'graph, 'points, F>(
+        &'buf mut self,
+        graph: &'graph Graph<T>,
+        start: &'points Vec2<T>,
+        goal: &'points Vec2<T>,
+        penalty: &'points T,
+        valid_points: F,
+    ) -> PathSearch<'buf, 'graph, 'points, T, F, S>
+    where
+        T: AddAssign<&'points T>,
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        self.clear();
+        self.push_starts(Some(start));
+        PathSearch {
+            buf: self,
+            graph,
+            goal,
+            penalty,
+            valid_points,
+            max_expansions: None,
+            max_cost: None,
+            expansions: 0,
+            limit_reached: false,
+            status: SearchStatus::Pending,
+        }
+    }
+
+    /// Clears the state of the last search.
+    fn clear(&mut self) {
         self.travelled.clear();
         self.predecessors.clear();
         self.cost_points.clear();
-        path
+        self.closest = None;
+    }
+
+    /// Pushes the given starting points into the search, with zero cost.
+    fn push_starts<'points, I>(&mut self, starts: I)
+    where
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        T: 'points,
+    {
+        for start in starts {
+            let state = SearchState { point: start.clone(), arrival: None };
+            self.travelled.insert(state.clone(), Cost::new());
+            self.cost_points.push(BinaryHeapEntry {
+                state,
+                cost: Cost::new(),
+                heuristic: T::zero(),
+            });
+        }
+    }
+
+    /// Assembles the path found till the given state, with the given cost.
+    fn assemble_path(&self, end: SearchState<T>, cost: T) -> Path<T> {
+        let mut steps = Vec::<DirecVector<_>>::new();
+        let mut current = &end;
+
+        // Starts are the only states without predecessors. Predecessors may
+        // be far away in a straight line, after a jump.
+        while let Some(prev) = self.predecessors.get(current) {
+            let vector =
+                Segment::from_ends(prev.point.clone(), current.point.clone())
+                    .unwrap()
+                    .vector;
+
+            match steps.last_mut() {
+                Some(step) if step.direction == vector.direction => {
+                    step.magnitude += vector.magnitude
+                },
+                _ => steps.push(vector),
+            }
+
+            current = prev;
+        }
+
+        steps.reverse();
+        Path::new(current.point.clone(), steps, cost)
     }
 }
 
+/// The status of an incremental search. See [`PathSearch::step`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SearchStatus<T> {
+    /// The search is not finished yet.
+    Pending,
+    /// A path was found.
+    Found(Path<T>),
+    /// There is no path, or a limit of the search was reached (see
+    /// [`PathSearch::limit_reached`]).
+    NoPath,
+}
+
+/// An incremental A* search, run a few expansions at a time. See
+/// [`PathMakerBuf::path_search`].
 #[derive(Debug)]
-struct PathMakerCall<'maker, 'graph, 'points, T, F>
+pub struct PathSearch<'buf, 'graph, 'points, T, F, S = RandomState>
 where
     T: Clone + Hash + Ord,
-    T: Zero + One,
-    T: AddAssign + CheckedAdd + CheckedSub + AddAssign<&'points T>,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    T: AddAssign<&'points T>,
     F: FnMut(&Vec2<T>) -> bool,
-    'graph: 'maker,
+    S: BuildHasher,
 {
-    buf: &'maker mut PathMakerBuf<T>,
-    graph: &'graph mut Graph<T>,
-    start: &'points Vec2<T>,
+    buf: &'buf mut PathMakerBuf<T, S>,
+    graph: &'graph Graph<T>,
     goal: &'points Vec2<T>,
     penalty: &'points T,
     valid_points: F,
+    max_expansions: Option<usize>,
+    max_cost: Option<T>,
+    expansions: usize,
+    limit_reached: bool,
+    status: SearchStatus<T>,
+}
+
+impl<'buf, 'graph, 'points, T, F, S> PathSearch<'buf, 'graph, 'points, T, F, S>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    T: AddAssign<&'points T>,
+    F: FnMut(&Vec2<T>) -> bool,
+    S: BuildHasher,
+{
+    /// Limits the total number of points expanded by the search. The search
+    /// gives up once the limit is reached.
+    pub fn max_expansions(self, max_expansions: usize) -> Self {
+        Self { max_expansions: Some(max_expansions), ..self }
+    }
+
+    /// Limits the cost of the path. The search gives up once every path left
+    /// would cost more than the limit.
+    pub fn max_cost(self, max_cost: T) -> Self {
+        Self { max_cost: Some(max_cost), ..self }
+    }
+
+    /// Runs the search for at most the given number of point expansions,
+    /// returning its status. Once the search is finished, the same status is
+    /// always returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     graph::{Graph, PathMakerBuf, SearchStatus},
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut graph = Graph::<u16>::new();
+    /// let start = Vec2 { x: 0, y: 0 };
+    /// let goal = Vec2 { x: 50, y: 0 };
+    /// graph.create_vertex(start);
+    /// graph.create_vertex(goal);
+    ///
+    /// let mut buf = PathMakerBuf::new();
+    /// let mut search = buf
+    ///     .path_search(&graph, &start, &goal, &2, |point| point.y == 0)
+    ///     .max_expansions(10);
+    /// assert_eq!(search.step(100), SearchStatus::NoPath);
+    /// assert!(search.limit_reached());
+    ///
+    /// // Heads towards the goal anyway.
+    /// let path = search.best_partial().unwrap();
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![DirecVector { direction: Direction::Right, magnitude: 9 }]
+    /// );
+    /// assert_eq!(path.cost, 9);
+    /// # }
+    /// ```
+    pub fn step(&mut self, max_expansions: usize) -> SearchStatus<T> {
+        if self.status != SearchStatus::Pending {
+            return self.status.clone();
+        }
+
+        let goal = self.goal;
+        let mut call = PathMakerCall::new(
+            &mut *self.buf,
+            self.graph,
+            |point| point == goal,
+            self.penalty,
+            unit_steps(&mut self.valid_points),
+            |point| point.clone().manhattan_dist(goal.clone()),
+        );
+
+        for _ in 0 .. max_expansions {
+            if matches!(self.max_expansions, Some(max) if self.expansions >= max)
+            {
+                self.limit_reached = true;
+                self.status = SearchStatus::NoPath;
+                break;
+            }
+
+            let within_cost =
+                match (call.buf.cost_points.peek(), &self.max_cost) {
+                    (None, _) => {
+                        self.status = SearchStatus::NoPath;
+                        break;
+                    },
+                    (Some(top), Some(max)) => top.cost.distance <= *max,
+                    (Some(_), None) => true,
+                };
+            if !within_cost {
+                self.limit_reached = true;
+                self.status = SearchStatus::NoPath;
+                break;
+            }
+
+            let current = call.buf.cost_points.pop().unwrap();
+            self.expansions += 1;
+            call.track_closest(&current.state);
+
+            if current.state.point == *goal {
+                let path = call
+                    .buf
+                    .assemble_path(current.state, current.cost.distance);
+                self.status = SearchStatus::Found(path);
+                break;
+            }
+
+            call.eval_neighbours(current.state);
+        }
+
+        self.status.clone()
+    }
+
+    /// How many points were expanded so far.
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    /// Whether the search gave up because of a limit set by
+    /// [`PathSearch::max_expansions`] or [`PathSearch::max_cost`].
+    pub fn limit_reached(&self) -> bool {
+        self.limit_reached
+    }
+
+    /// The best partial path found so far: the path till the
+    /// expanded point closest to the goal (the cheapest one, among equally
+    /// close points). Useful for heading towards the goal when the search
+    /// gives up or is not finished yet. Returns `None` only if no point was
+    /// expanded yet.
+    pub fn best_partial(&self) -> Option<Path<T>> {
+        let (_, cost, state) = self.buf.closest.as_ref()?;
+        Some(self.buf.assemble_path(state.clone(), cost.distance.clone()))
+    }
+}
+
+/// Step cost function where every step into a valid point costs one.
+fn unit_steps<T, F>(
+    mut valid_points: F,
+) -> impl FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>
+where
+    T: One,
+    F: FnMut(&Vec2<T>) -> bool,
+{
+    move |_, point, _| {
+        if valid_points(point) {
+            Some(T::one())
+        } else {
+            None
+        }
+    }
 }
 
-impl<'maker, 'graph, 'points, T, F> PathMakerCall<'maker, 'graph, 'points, T, F>
+#[derive(Debug)]
+struct PathMakerCall<'maker, 'graph, 'points, T, S, G, C, H>
 where
     T: Clone + Hash + Ord,
     T: Zero + One,
     T: AddAssign + CheckedAdd + CheckedSub + AddAssign<&'points T>,
-    F: FnMut(&Vec2<T>) -> bool,
+    S: BuildHasher,
+    G: FnMut(&Vec2<T>) -> bool,
+    C: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    H: FnMut(&Vec2<T>) -> T,
+    'graph: 'maker,
+{
+    buf: &'maker mut PathMakerBuf<T, S>,
+    graph: &'graph Graph<T>,
+    is_goal: G,
+    penalty: &'points T,
+    step_cost: C,
+    heuristic: H,
+    jumping: bool,
+}
+
+impl<'maker, 'graph, 'points, T, S, G, C, H>
+    PathMakerCall<'maker, 'graph, 'points, T, S, G, C, H>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One,
+    T: AddAssign + CheckedAdd + CheckedSub + AddAssign<&'points T>,
+    S: BuildHasher,
+    G: FnMut(&Vec2<T>) -> bool,
+    C: FnMut(&Vec2<T>, &Vec2<T>, Direction) -> Option<T>,
+    H: FnMut(&Vec2<T>) -> T,
 {
     fn new(
-        buf: &'maker mut PathMakerBuf<T>,
-        graph: &'graph mut Graph<T>,
-        start: &'points Vec2<T>,
-        goal: &'points Vec2<T>,
+        buf: &'maker mut PathMakerBuf<T, S>,
+        graph: &'graph Graph<T>,
+        is_goal: G,
         penalty: &'points T,
-        valid_points: F,
+        step_cost: C,
+        heuristic: H,
     ) -> Self {
-        let this = Self { buf, graph, start, goal, penalty, valid_points };
-        this.buf.travelled.insert(this.start.clone(), Cost::new());
-        this.buf.cost_points.push(BinaryHeapEntry {
-            point: this.start.clone(),
-            cost: Cost::new(),
-        });
-        this
+        Self {
+            buf,
+            graph,
+            is_goal,
+            penalty,
+            step_cost,
+            heuristic,
+            jumping: false,
+        }
     }
 
-    fn run(&mut self) -> Option<Vec<DirecVector<T>>> {
+    fn run(&mut self) -> Option<Path<T>> {
         loop {
             let current = self.buf.cost_points.pop()?;
+            self.buf.expansions += 1;
 
-            if current.point == *self.goal {
-                break Some(self.assemble_path(current.cost));
+            if (self.is_goal)(&current.state.point) {
+                let cost = current.cost.distance;
+                break Some(self.buf.assemble_path(current.state, cost));
             }
 
-            self.eval_neighbours(current.point);
+            if self.jumping {
+                self.eval_jumps(current.state);
+            } else {
+                self.eval_neighbours(current.state);
+            }
         }
     }
 
-    fn assemble_path(&mut self, _cost: Cost<T>) -> Vec<DirecVector<T>> {
-        let mut steps = Vec::<DirecVector<_>>::new();
-        let mut last_vertex = self.goal;
-        let mut current = self.goal;
+    /// Records the given expanded state as the closest one to the goal, if it
+    /// is closer than the previous one.
+    fn track_closest(&mut self, state: &SearchState<T>) {
+        let heuristic = (self.heuristic)(&state.point);
+        let cost = self.buf.travelled.get(state).unwrap().clone();
+        let candidate = (heuristic, cost, state.clone());
+        let is_closer = match &self.buf.closest {
+            Some(closest) => candidate < *closest,
+            None => true,
+        };
+        if is_closer {
+            self.buf.closest = Some(candidate);
+        }
+    }
+
+    /// Tests whether the path can step from the current state in the given
+    /// direction, given how the graph deals with crossing edges.
+    fn can_step(&self, current: &SearchState<T>, direction: Direction) -> bool {
+        if self.graph.planarity != Planarity::Forbid {
+            return true;
+        }
+        match self.edge_axis(&current.point) {
+            Some(axis) if axis != direction.axis() => {
+                let previous = self.buf.predecessors.get(current);
+                previous
+                    .and_then(|prev| prev.point.direction_to(&current.point))
+                    != Some(direction)
+            },
+            _ => true,
+        }
+    }
 
-        while current != self.start {
-            let prev = self.buf.predecessors.get(current).unwrap();
-            let direction = prev.direction_to(current).unwrap();
+    /// The axis of the edge the given point is in the middle of, if any.
+    fn edge_axis(&self, point: &Vec2<T>) -> Option<Axis> {
+        self.graph
+            .edge_at::<T>(point.as_ref())
+            .and_then(|(start, end)| start.direction_to(&end))
+            .map(Direction::axis)
+    }
 
-            match steps.last_mut() {
-                Some(step) if step.direction == direction => {
-                    step.magnitude += T::one()
-                },
-                _ => {
-                    if last_vertex != current {
-                        self.graph.create_vertex((*current).clone());
-                        self.graph.connect::<T>(
-                            last_vertex.as_ref(),
-                            current.as_ref(),
-                        );
-                        last_vertex = current;
-                    }
-                    steps.push(DirecVector { magnitude: T::one(), direction });
-                },
+    /// The state of the given point reached by moving in the given
+    /// direction. The arrival axis is only kept where it matters, i.e. in the
+    /// middle of edges when crossings are forbidden.
+    fn state_at(&self, point: Vec2<T>, direction: Direction) -> SearchState<T> {
+        let arrival = if self.graph.planarity == Planarity::Forbid
+            && self.edge_axis(&point).is_some()
+        {
+            Some(direction.axis())
+        } else {
+            None
+        };
+        SearchState { point, arrival }
+    }
+
+    fn eval_neighbours(&mut self, current: SearchState<T>) {
+        for direction in Direction::iter() {
+            let neighbour = current
+                .point
+                .clone()
+                .checked_move(direction)
+                .filter(|_| self.can_step(&current, direction));
+            let step = neighbour.as_ref().and_then(|neighbour| {
+                (self.step_cost)(&current.point, neighbour, direction)
+            });
+            if let (Some(neighbour), Some(step)) = (neighbour, step) {
+                self.relax(&current, neighbour, step, direction);
             }
+        }
+    }
 
-            if self.graph.vertices_edges().contains::<T>(prev.as_ref()) {
-                self.graph.connect::<T>(last_vertex.as_ref(), prev.as_ref());
-                last_vertex = prev;
+    /// Evaluates the points reached by jumping from the current point in
+    /// every direction but backwards. This is the jump point search
+    /// counterpart of `eval_neighbours`, for steps which all cost the same.
+    fn eval_jumps(&mut self, current: SearchState<T>) {
+        let arrival = self
+            .buf
+            .predecessors
+            .get(&current)
+            .and_then(|prev| prev.point.direction_to(&current.point));
+        for direction in Direction::iter() {
+            if arrival == Some(!direction) {
+                continue;
+            }
+            if let Some((point, distance)) =
+                self.jump(&current.point, direction)
+            {
+                self.relax(&current, point, distance, direction);
             }
+        }
+    }
 
-            current = prev;
+    /// Moves from the given point in the given direction until reaching a
+    /// point where the path might need to stop or turn: a goal, a point with
+    /// a forced neighbour, or, when moving vertically, a point from which a
+    /// horizontal jump succeeds. Returns the point reached and the cost of
+    /// the jump, or `None` if an obstacle is hit first.
+    fn jump(
+        &mut self,
+        from: &Vec2<T>,
+        direction: Direction,
+    ) -> Option<(Vec2<T>, T)> {
+        let mut point = from.clone();
+        let mut distance = T::zero();
+        let mut moved = 0;
+        loop {
+            let next = point.clone().checked_move(direction)?;
+            distance += (self.step_cost)(&point, &next, direction)?;
+            point = next;
+            moved += 1;
+
+            let stops = (self.is_goal)(&point)
+                || matches!(self.buf.options.max_jump, Some(max) if moved >= max)
+                || self.has_forced_neighbour(&point, direction)
+                || (direction.axis() == Axis::Y
+                    && (self.jump(&point, Direction::Left).is_some()
+                        || self.jump(&point, Direction::Right).is_some()));
+            if stops {
+                break Some((point, distance));
+            }
         }
+    }
 
-        steps.reverse();
-        steps
+    /// Tests whether a point reached by a step in the given direction has a
+    /// forced neighbour, i.e. a side neighbour that can only be reached
+    /// optimally through this point, because the point behind it is blocked.
+    fn has_forced_neighbour(
+        &mut self,
+        point: &Vec2<T>,
+        direction: Direction,
+    ) -> bool {
+        let behind = point.clone().move_one(!direction);
+        let sides =
+            [direction.rotate_clockwise(), direction.rotate_countercw()];
+        sides.iter().any(|&side| {
+            self.can_move(point, side) && !self.can_move(&behind, side)
+        })
     }
 
-    fn eval_neighbours(&mut self, current: Vec2<T>) {
-        for direction in Direction::iter() {
-            if let Some(neighbour) = current
-                .clone()
-                .checked_move(direction)
-                .filter(|point| (self.valid_points)(point))
-            {
-                let mut attempt =
-                    self.buf.travelled.get(&current).unwrap().clone();
-                attempt.distance += T::one();
+    /// Tests whether a step from the given point in the given direction is
+    /// valid.
+    fn can_move(&mut self, from: &Vec2<T>, direction: Direction) -> bool {
+        match from.clone().checked_move(direction) {
+            Some(to) => (self.step_cost)(from, &to, direction).is_some(),
+            None => false,
+        }
+    }
 
-                let is_turning = self
-                    .buf
-                    .predecessors
-                    .get(&current)
-                    .map(|prev| prev.direction_to(&current) != Some(direction))
-                    .unwrap_or(false);
-
-                if is_turning {
-                    attempt.turns += T::one();
-                    attempt.distance += self.penalty;
-                }
+    /// Records the given point as reached from the current state by moving
+    /// straight in the given direction, with the given cost, if this is
+    /// cheaper than the previous way of reaching its state.
+    fn relax(
+        &mut self,
+        current: &SearchState<T>,
+        neighbour: Vec2<T>,
+        step: T,
+        direction: Direction,
+    ) {
+        let mut attempt = self.buf.travelled.get(current).unwrap().clone();
+        attempt.distance += step;
+
+        let is_turning = self
+            .buf
+            .predecessors
+            .get(current)
+            .map(|prev| {
+                prev.point.direction_to(&current.point) != Some(direction)
+            })
+            .unwrap_or(false);
+
+        if is_turning {
+            attempt.turns += T::one();
+            attempt.distance += self.penalty;
+        }
 
-                if self
-                    .buf
-                    .travelled
-                    .get(&neighbour)
-                    .map_or(true, |cost| attempt < *cost)
-                {
-                    self.buf
-                        .predecessors
-                        .insert(neighbour.clone(), current.clone());
-                    self.buf
-                        .travelled
-                        .insert(neighbour.clone(), attempt.clone());
-                    let heuristics = neighbour
-                        .clone()
-                        .zip_with(self.goal.clone(), Distance::distance)
-                        .fold(T::zero(), |coord_a, coord_b| coord_a + coord_b);
-                    attempt.distance += heuristics;
-                    self.buf.cost_points.push(BinaryHeapEntry {
-                        point: neighbour,
-                        cost: attempt,
-                    });
-                }
-            }
+        let neighbour = self.state_at(neighbour, direction);
+        let improves = match self.buf.travelled.get(&neighbour) {
+            Some(cost) => attempt < *cost,
+            None => true,
+        };
+        if improves {
+            self.buf.predecessors.insert(neighbour.clone(), current.clone());
+            self.buf.travelled.insert(neighbour.clone(), attempt.clone());
+            let heuristic = (self.heuristic)(&neighbour.point);
+            attempt.distance += heuristic.clone();
+            self.buf.cost_points.push(BinaryHeapEntry {
+                state: neighbour,
+                cost: attempt,
+                heuristic,
+            });
         }
     }
 }
@@ -718,10 +2670,29 @@ impl<T> Cost<T> {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+/// A state of a path search: a point, and the axis along which it was
+/// reached, if it is in the middle of an edge and crossings are forbidden.
+/// There, a path arriving along the edge may leave it on either side, while a
+/// path arriving across the edge may not go on straight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct SearchState<T> {
+    point: Vec2<T>,
+    arrival: Option<Axis>,
+}
+
+#[derive(Debug, Clone, Copy)]
 struct BinaryHeapEntry<T> {
     cost: Cost<T>,
-    point: Vec2<T>,
+    heuristic: T,
+    state: SearchState<T>,
+}
+
+impl<T> BinaryHeapEntry<T> {
+    /// Key of the entry in the heap, so that ties between equal costs are
+    /// broken deterministically. See [`PathMakerBuf`].
+    fn key(&self) -> (&Cost<T>, &T, &SearchState<T>) {
+        (&self.cost, &self.heuristic, &self.state)
+    }
 }
 
 impl<T> PartialEq for BinaryHeapEntry<T>
@@ -729,7 +2700,7 @@ where
     T: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
+        self.key() == other.key()
     }
 }
 
@@ -740,7 +2711,7 @@ where
     T: PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.cost.partial_cmp(&other.cost).map(Ordering::reverse)
+        self.key().partial_cmp(&other.key()).map(Ordering::reverse)
     }
 }
 
@@ -749,7 +2720,7 @@ where
     T: Ord,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.cost.cmp(&other.cost).reverse()
+        self.key().cmp(&other.key()).reverse()
     }
 }
 
@@ -761,7 +2732,7 @@ where
     where
         H: Hasher,
     {
-        self.cost.hash(state)
+        self.key().hash(state)
     }
 }
 
@@ -803,6 +2774,49 @@ where
     }
 }
 
+/// Iterator over the edges of the graph as segments. See [`Graph::segments`].
+#[derive(Debug, Clone)]
+pub struct Segments<'graph, T>
+where
+    T: Ord,
+{
+    connections: Connections<'graph, T>,
+}
+
+impl<'graph, T> Iterator for Segments<'graph, T>
+where
+    T: Ord + Clone + Sub<Output = T> + Zero,
+{
+    type Item = Segment<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex, neighbour) = self.connections.next()?;
+        let segment = Segment::from_ends(vertex.cloned(), neighbour.cloned());
+        Some(segment.expect("Inconsistent graph"))
+    }
+}
+
+/// A pair of crossing edges. See [`Graph::crossings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crossing<'graph, T> {
+    /// The horizontal edge, from left to right.
+    pub horizontal: (Vec2<&'graph T>, Vec2<&'graph T>),
+    /// The vertical edge, from top to bottom.
+    pub vertical: (Vec2<&'graph T>, Vec2<&'graph T>),
+    /// The point where both edges cross.
+    pub point: Vec2<&'graph T>,
+}
+
+/// Event of the sweep line in [`Graph::crossings`]. At the same X coordinate,
+/// horizontal edges leave the sweep line before vertical edges are checked
+/// against it, and enter it only afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum SweepEvent {
+    Leave,
+    Cross,
+    Enter,
+}
+
 /// Iterator over connected components of the graph. See [`Graph::components`].
 #[derive(Debug, Clone)]
 pub struct Components<'graph, T>
@@ -1,7 +1,29 @@
-use super::Graph;
+use super::{
+    Graph,
+    PathMakerBuf,
+    Planarity,
+    Route,
+    SearchOptions,
+    SearchStatus,
+    SearchStrategy,
+};
 use crate::{
+    axis::Axis,
     coord::Vec2,
     direc::{DirecMap, DirecVector, Direction},
+    path::Path,
+    segment::Segment,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::{
+        btree_map::Entry,
+        hash_map::DefaultHasher,
+        BTreeMap,
+        BTreeSet,
+        VecDeque,
+    },
+    hash::BuildHasherDefault,
 };
 
 #[test]
@@ -231,6 +253,101 @@ fn connections() {
     );
 }
 
+#[test]
+fn segments() {
+    let graph = make_graph();
+    let segments: Vec<_> = graph.segments().collect();
+    let connections: Vec<_> = graph
+        .connections()
+        .map(|(va, vb)| Segment::from_ends(va.copied(), vb.copied()).unwrap())
+        .collect();
+    assert_eq!(segments, connections);
+    for segment in segments {
+        assert!(matches!(
+            segment.vector.direction,
+            Direction::Down | Direction::Right
+        ));
+    }
+}
+
+/// A random graph in the square `[0, 12)`, with roughly half of the possible
+/// edges.
+fn random_graph(seed: u64) -> Graph<i32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut graph = Graph::new();
+    for _ in 0 .. 40 {
+        graph.create_vertex(Vec2 {
+            x: rng.gen_range(0 .. 12),
+            y: rng.gen_range(0 .. 12),
+        });
+    }
+    let vertices: Vec<_> = graph
+        .vertices_edges()
+        .rows()
+        .map(|(vertex, _)| vertex.copied())
+        .collect();
+    for vertex in vertices {
+        for axis in Axis::iter() {
+            let direction = Direction::from_axis_pos(axis);
+            let neighbour = graph
+                .vertices_edges()
+                .first_neighbour(vertex.as_ref(), direction)
+                .map(Vec2::copied);
+            if let Some(neighbour) = neighbour {
+                if rng.gen_bool(0.5) {
+                    graph.connect(vertex.as_ref(), neighbour.as_ref());
+                }
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+fn crossings() {
+    for seed in 0 .. 20 {
+        let graph = random_graph(seed);
+        let segments: Vec<_> = graph.segments().collect();
+        let mut expected = BTreeSet::new();
+        for horizontal in &segments {
+            for vertical in &segments {
+                if horizontal.axis() != Axis::X || vertical.axis() != Axis::Y {
+                    continue;
+                }
+                let point = Vec2 { x: vertical.start.x, y: horizontal.start.y };
+                let interior = |segment: &Segment<i32>| {
+                    segment.contains(&point)
+                        && segment.start != point
+                        && segment.end() != point
+                };
+                if interior(horizontal) && interior(vertical) {
+                    expected.insert((*horizontal, *vertical, point));
+                }
+            }
+        }
+
+        let crossings = graph.crossings();
+        let found: BTreeSet<_> = crossings
+            .iter()
+            .map(|crossing| {
+                let edge = |(start, end): (Vec2<&i32>, Vec2<&i32>)| {
+                    Segment::from_ends(start.copied(), end.copied()).unwrap()
+                };
+                (
+                    edge(crossing.horizontal),
+                    edge(crossing.vertical),
+                    crossing.point.copied(),
+                )
+            })
+            .collect();
+        assert_eq!(found.len(), crossings.len(), "seed {}", seed);
+        assert_eq!(found, expected, "seed {}", seed);
+        assert_eq!(graph.is_planar_embedding(), expected.is_empty());
+    }
+    assert!(make_graph().is_planar_embedding());
+    assert!(Graph::<u8>::new().is_planar_embedding());
+}
+
 #[test]
 fn connect_twice() {
     let mut graph = make_graph();
@@ -724,3 +841,1075 @@ fn a_star_irregular() {
 
     assert_eq!(graph, expected);
 }
+
+/// Checks that edge flags agree between neighbour vertices.
+fn assert_consistent(graph: &Graph<i32>) {
+    for (vertex, edges) in graph.vertices_edges().rows() {
+        for direction in Direction::iter() {
+            let neighbour =
+                graph.vertices_edges().first_neighbour_data(vertex, direction);
+            match neighbour {
+                Some((_, neighbour_edges)) => {
+                    assert_eq!(edges[direction], neighbour_edges[!direction])
+                },
+                None => assert!(!edges[direction]),
+            }
+        }
+    }
+}
+
+fn make_wall_graph(planarity: Planarity) -> Graph<i32> {
+    let mut graph = Graph::with_planarity(planarity);
+    graph.extend_vertices(vec![
+        Vec2 { x: 0, y: 5 },
+        Vec2 { x: 10, y: 5 },
+        Vec2 { x: 5, y: 0 },
+        Vec2 { x: 5, y: 10 },
+    ]);
+    graph.connect(Vec2 { x: 0, y: 5 }.as_ref(), Vec2 { x: 10, y: 5 }.as_ref());
+    graph
+}
+
+/// Connecting must not require cloning the coordinates.
+#[allow(dead_code)]
+fn connect_without_clone<T>(graph: &mut Graph<T>, edge: (Vec2<&T>, Vec2<&T>))
+where
+    T: Ord,
+{
+    graph.connect(edge.0, edge.1);
+    graph.extend_edges(Some(edge));
+}
+
+#[test]
+fn connect_planar() {
+    let mut graph = make_wall_graph(Planarity::Forbid);
+    let top = Vec2 { x: 5, y: 0 };
+    let bottom = Vec2 { x: 5, y: 10 };
+    assert!(!graph.connect(top.as_ref(), bottom.as_ref()));
+    assert!(!graph.are_connected(top.as_ref(), bottom.as_ref()));
+    assert!(graph.is_planar_embedding());
+
+    let mut graph = make_wall_graph(Planarity::Junction);
+    graph.extend_vertices(vec![Vec2 { x: -3, y: 7 }, Vec2 { x: 12, y: 7 }]);
+    graph.connect(Vec2 { x: -3, y: 7 }.as_ref(), Vec2 { x: 12, y: 7 }.as_ref());
+    assert!(!graph.connect(bottom.as_ref(), top.as_ref()));
+    assert!(graph.connect_with_junctions(bottom.as_ref(), top.as_ref()));
+    assert_consistent(&graph);
+    assert!(graph.is_planar_embedding());
+    let connections: Vec<_> = graph
+        .connections()
+        .map(|(va, vb)| (va.copied(), vb.copied()))
+        .collect();
+    assert_eq!(
+        connections,
+        &[
+            (Vec2 { x: 5, y: 0 }, Vec2 { x: 5, y: 5 }),
+            (Vec2 { x: 0, y: 5 }, Vec2 { x: 5, y: 5 }),
+            (Vec2 { x: 5, y: 5 }, Vec2 { x: 5, y: 7 }),
+            (Vec2 { x: 5, y: 5 }, Vec2 { x: 10, y: 5 }),
+            (Vec2 { x: -3, y: 7 }, Vec2 { x: 5, y: 7 }),
+            (Vec2 { x: 5, y: 7 }, Vec2 { x: 5, y: 10 }),
+            (Vec2 { x: 5, y: 7 }, Vec2 { x: 12, y: 7 }),
+        ]
+    );
+
+    assert!(!graph.remove_vertex(Vec2 { x: 5, y: 5 }.as_ref()));
+    assert!(graph.set_planarity(Planarity::Free));
+    assert!(graph.remove_vertex(Vec2 { x: 5, y: 5 }.as_ref()));
+    assert_consistent(&graph);
+    assert!(!graph.is_planar_embedding());
+    assert!(!graph.set_planarity(Planarity::Forbid));
+    assert_eq!(graph.planarity(), Planarity::Free);
+}
+
+#[test]
+fn create_vertex_splits_edge() {
+    let mut graph = make_wall_graph(Planarity::Free);
+    assert!(graph.create_vertex(Vec2 { x: 3, y: 5 }));
+    assert_consistent(&graph);
+    assert!(graph.are_connected(
+        Vec2 { x: 0, y: 5 }.as_ref(),
+        Vec2 { x: 3, y: 5 }.as_ref()
+    ));
+    assert!(graph.are_connected(
+        Vec2 { x: 3, y: 5 }.as_ref(),
+        Vec2 { x: 10, y: 5 }.as_ref()
+    ));
+}
+
+#[test]
+fn a_star_planar() {
+    let start = Vec2 { x: 5, y: 0 };
+    let goal = Vec2 { x: 5, y: 10 };
+    let valid_points = |point: &Vec2<i32>| {
+        (-3 ..= 13).contains(&point.x) && (0 ..= 10).contains(&point.y)
+    };
+
+    let mut graph = make_wall_graph(Planarity::Free);
+    let steps = graph.make_path(&start, &goal, &2, valid_points).unwrap();
+    assert_eq!(
+        steps,
+        vec![DirecVector { direction: Direction::Down, magnitude: 10 }]
+    );
+    assert!(!graph.is_planar_embedding());
+
+    // The path joins the wall and leaves it, instead of crossing it.
+    let mut graph = make_wall_graph(Planarity::Forbid);
+    let steps = graph.make_path(&start, &goal, &2, valid_points).unwrap();
+    let length: i32 = steps.iter().map(|step| step.magnitude).sum();
+    assert!(length < 20);
+    assert!(graph.is_planar_embedding());
+    assert_consistent(&graph);
+    let t_junctions: Vec<_> = graph
+        .vertices_edges()
+        .rows()
+        .filter(|(vertex, _)| *vertex.y == 5 && (1 .. 10).contains(vertex.x))
+        .map(|(_, edges)| edges.iter().filter(|(_, &edge)| edge).count())
+        .collect();
+    assert_eq!(t_junctions, &[3, 3]);
+
+    let mut graph = make_wall_graph(Planarity::Junction);
+    let steps = graph.make_path(&start, &goal, &2, valid_points).unwrap();
+    assert_eq!(
+        steps,
+        vec![DirecVector { direction: Direction::Down, magnitude: 10 }]
+    );
+    assert!(graph.is_planar_embedding());
+    assert_consistent(&graph);
+    let junction = Vec2 { x: 5, y: 5 };
+    assert_eq!(
+        graph.vertex_edges(junction.as_ref()),
+        Some(DirecMap::from_direcs(|_| true))
+    );
+}
+
+#[test]
+fn edge_at() {
+    let mut graph = make_wall_graph(Planarity::Free);
+    graph.connect(Vec2 { x: 5, y: 0 }.as_ref(), Vec2 { x: 5, y: 10 }.as_ref());
+    let wall = (Vec2 { x: &0, y: &5 }, Vec2 { x: &10, y: &5 });
+    let column = (Vec2 { x: &5, y: &0 }, Vec2 { x: &5, y: &10 });
+    assert_eq!(graph.edge_at(Vec2 { x: 1, y: 5 }.as_ref()), Some(wall));
+    assert_eq!(graph.edge_at(Vec2 { x: 9, y: 5 }.as_ref()), Some(wall));
+    assert_eq!(graph.edge_at(Vec2 { x: 5, y: 9 }.as_ref()), Some(column));
+    assert_eq!(graph.edge_at(Vec2 { x: 5, y: 5 }.as_ref()), Some(column));
+    assert_eq!(graph.edge_at(Vec2 { x: 0, y: 5 }.as_ref()), None);
+    assert_eq!(graph.edge_at(Vec2 { x: 11, y: 5 }.as_ref()), None);
+    assert_eq!(graph.edge_at(Vec2 { x: 1, y: 4 }.as_ref()), None);
+
+    assert!(!graph.split_edge_at(Vec2 { x: 1, y: 4 }));
+    assert!(!graph.split_edge_at(Vec2 { x: 10, y: 5 }));
+    assert!(graph.split_edge_at(Vec2 { x: 8, y: 5 }));
+    assert_consistent(&graph);
+    assert_eq!(
+        graph.edge_at(Vec2 { x: 9, y: 5 }.as_ref()),
+        Some((Vec2 { x: &8, y: &5 }, Vec2 { x: &10, y: &5 }))
+    );
+    assert!(graph.split_edge_at(Vec2 { x: 5, y: 5 }));
+    assert_consistent(&graph);
+    assert_eq!(
+        graph.vertex_edges(Vec2 { x: 5, y: 5 }.as_ref()),
+        Some(DirecMap::from_direcs(|_| true))
+    );
+    assert!(graph.is_planar_embedding());
+}
+
+#[test]
+fn a_star_t_junction() {
+    for &planarity in &[Planarity::Free, Planarity::Forbid, Planarity::Junction]
+    {
+        let mut graph = make_wall_graph(planarity);
+        let start = Vec2 { x: 5, y: 0 };
+        let goal = Vec2 { x: 10, y: 5 };
+        let steps = graph
+            .make_path(&start, &goal, &2, |point| {
+                point.x == 5 && (0 .. 5).contains(&point.y)
+                    || point.y == 5 && (0 ..= 10).contains(&point.x)
+            })
+            .unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                DirecVector { direction: Direction::Down, magnitude: 5 },
+                DirecVector { direction: Direction::Right, magnitude: 5 },
+            ]
+        );
+        assert_consistent(&graph);
+        assert_eq!(
+            graph.vertex_edges(Vec2 { x: 5, y: 5 }.as_ref()),
+            Some(DirecMap { up: true, left: true, down: false, right: true })
+        );
+
+        // Starting in the middle of an edge.
+        let mut graph = make_wall_graph(planarity);
+        let start = Vec2 { x: 3, y: 5 };
+        let goal = Vec2 { x: 3, y: 9 };
+        graph.create_vertex(goal);
+        let steps =
+            graph.make_path(&start, &goal, &2, |point| point.x == 3).unwrap();
+        assert_eq!(
+            steps,
+            vec![DirecVector { direction: Direction::Down, magnitude: 4 }]
+        );
+        assert_consistent(&graph);
+        assert_eq!(
+            graph.vertex_edges(start.as_ref()),
+            Some(DirecMap { up: false, left: true, down: true, right: true })
+        );
+    }
+}
+
+#[test]
+fn a_star_planar_random() {
+    for &planarity in &[Planarity::Forbid, Planarity::Junction] {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut graph = Graph::with_planarity(planarity);
+        let mut vertices = Vec::new();
+        for _ in 0 .. 12 {
+            let vertex =
+                Vec2 { x: rng.gen_range(0 .. 16), y: rng.gen_range(0 .. 16) };
+            graph.create_vertex(vertex);
+            vertices.push(vertex);
+        }
+        for _ in 0 .. 30 {
+            let start = vertices[rng.gen_range(0 .. vertices.len())];
+            let goal = vertices[rng.gen_range(0 .. vertices.len())];
+            graph.make_path(&start, &goal, &2, |point| {
+                (0 .. 16).contains(&point.x) && (0 .. 16).contains(&point.y)
+            });
+            assert!(graph.is_planar_embedding(), "{:?}", planarity);
+            assert_consistent(&graph);
+        }
+    }
+}
+
+#[test]
+fn forbid_keeps_arrivals_along_edges() {
+    let mut graph = Graph::with_planarity(Planarity::Forbid);
+    let left = Vec2 { x: 5, y: 7 };
+    let right = Vec2 { x: 9, y: 7 };
+    graph.create_vertex(left);
+    graph.create_vertex(right);
+    graph.connect(left.as_ref(), right.as_ref());
+    let valid_points = |point: &Vec2<i32>| {
+        (0 .. 10).contains(&point.x) && (0 .. 10).contains(&point.y)
+    };
+
+    // The path must join the edge to cross its axis.
+    let start = Vec2 { x: 6, y: 6 };
+    let path = graph.find_path(&start, &Vec2 { x: 8, y: 9 }, &0, valid_points);
+    let path = path.unwrap();
+    assert_eq!(path.length(), 5);
+    assert!(graph.commit_path(&path.start, &path.steps));
+    assert!(graph.is_planar_embedding());
+    assert_consistent(&graph);
+}
+
+/// Length of the shortest path which does not cross edges of the graph,
+/// found by a breadth-first search over points and arrival directions.
+fn shortest_planar_length<F>(
+    graph: &Graph<i32>,
+    start: Vec2<i32>,
+    goal: Vec2<i32>,
+    valid_points: F,
+) -> Option<i32>
+where
+    F: Fn(&Vec2<i32>) -> bool,
+{
+    let mut distances = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert((start, None), 0);
+    queue.push_back((start, None::<Direction>));
+    while let Some((point, arrival)) = queue.pop_front() {
+        let distance = distances[&(point, arrival)];
+        if point == goal {
+            return Some(distance);
+        }
+        let edge_axis = graph
+            .edge_at(point.as_ref())
+            .and_then(|(start, end)| start.direction_to(&end))
+            .map(Direction::axis);
+        for direction in Direction::iter() {
+            let crosses = matches!(
+                edge_axis,
+                Some(axis) if axis != direction.axis()
+                    && arrival == Some(direction)
+            );
+            let next = point.move_one(direction);
+            if crosses || !valid_points(&next) {
+                continue;
+            }
+            let state = (next, Some(direction));
+            if let Entry::Vacant(entry) = distances.entry(state) {
+                entry.insert(distance + 1);
+                queue.push_back(state);
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn forbid_finds_shortest_paths() {
+    let mut rng = StdRng::seed_from_u64(23);
+    let valid_points = |point: &Vec2<i32>| {
+        (0 .. 12).contains(&point.x) && (0 .. 12).contains(&point.y)
+    };
+    for _ in 0 .. 20 {
+        let mut graph = Graph::with_planarity(Planarity::Forbid);
+        let mut vertices = Vec::new();
+        for _ in 0 .. 8 {
+            let vertex =
+                Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+            graph.create_vertex(vertex);
+            vertices.push(vertex);
+        }
+        for _ in 0 .. 8 {
+            let start = vertices[rng.gen_range(0 .. vertices.len())];
+            let goal = vertices[rng.gen_range(0 .. vertices.len())];
+            graph.make_path(&start, &goal, &0, valid_points);
+        }
+        for _ in 0 .. 30 {
+            let start =
+                Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+            let goal =
+                Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+            let path = graph.find_path(&start, &goal, &0, valid_points);
+            let expected =
+                shortest_planar_length(&graph, start, goal, valid_points);
+            assert_eq!(
+                path.as_ref().map(|path| path.length()),
+                expected,
+                "from {} to {}",
+                start,
+                goal
+            );
+            if let Some(path) = path {
+                let mut committed = graph.clone();
+                assert!(committed.commit_path(&path.start, &path.steps));
+                assert!(committed.is_planar_embedding());
+            }
+        }
+    }
+}
+
+#[test]
+fn find_and_commit_path() {
+    for &planarity in &[Planarity::Free, Planarity::Forbid, Planarity::Junction]
+    {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut graph = Graph::with_planarity(planarity);
+        let mut vertices = Vec::new();
+        for _ in 0 .. 10 {
+            let vertex =
+                Vec2 { x: rng.gen_range(0 .. 14), y: rng.gen_range(0 .. 14) };
+            graph.create_vertex(vertex);
+            vertices.push(vertex);
+        }
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 14).contains(&point.x) && (0 .. 14).contains(&point.y)
+        };
+        for _ in 0 .. 20 {
+            let start = vertices[rng.gen_range(0 .. vertices.len())];
+            let goal = vertices[rng.gen_range(0 .. vertices.len())];
+            let before = graph.clone();
+            let found = graph.find_path(&start, &goal, &2, valid_points);
+            assert_eq!(graph, before);
+            let made = graph.make_path(&start, &goal, &2, valid_points);
+            assert_eq!(found.clone().map(|path| path.steps), made);
+
+            if let Some(path) = found {
+                assert_eq!(path.start, start);
+                assert_eq!(path.end(), goal);
+                assert_eq!(path.cost, path.length() + 2 * path.turns() as i32);
+                let mut committed = before;
+                assert!(committed.commit_path(&start, &path.steps));
+                assert_eq!(committed, graph);
+            }
+        }
+    }
+
+    let mut graph = Graph::<i32>::new();
+    let start = Vec2 { x: 2, y: 2 };
+    graph.commit_path(
+        &start,
+        &[
+            DirecVector { direction: Direction::Up, magnitude: 3 },
+            DirecVector { direction: Direction::Left, magnitude: 1 },
+        ],
+    );
+    assert!(graph.are_connected(start.as_ref(), Vec2 { x: 2, y: -1 }.as_ref()));
+    assert!(graph.are_connected(
+        Vec2 { x: 2, y: -1 }.as_ref(),
+        Vec2 { x: 1, y: -1 }.as_ref()
+    ));
+    assert_eq!(graph.vertices_edges().len(), 3);
+}
+
+#[test]
+fn commit_path_forbid() {
+    let mut graph = Graph::<i32>::with_planarity(Planarity::Forbid);
+    let top = Vec2 { x: 2, y: 0 };
+    let bottom = Vec2 { x: 2, y: 4 };
+    graph.create_vertex(top);
+    graph.create_vertex(bottom);
+    graph.connect(top.as_ref(), bottom.as_ref());
+
+    // Straight through the middle of the edge.
+    let before = graph.clone();
+    let across = [
+        DirecVector { direction: Direction::Right, magnitude: 4 },
+        DirecVector { direction: Direction::Down, magnitude: 1 },
+    ];
+    assert!(!graph.commit_path(&Vec2 { x: 0, y: 2 }, &across));
+    assert_eq!(graph, before);
+
+    // Crossing itself.
+    let looping = [
+        DirecVector { direction: Direction::Right, magnitude: 2 },
+        DirecVector { direction: Direction::Up, magnitude: 1 },
+        DirecVector { direction: Direction::Left, magnitude: 1 },
+        DirecVector { direction: Direction::Down, magnitude: 2 },
+    ];
+    assert!(!graph.commit_path(&Vec2 { x: 5, y: 2 }, &looping));
+    assert_eq!(graph, before);
+
+    // Turning at the middle of the edge forms a T-junction.
+    let touching = [
+        DirecVector { direction: Direction::Right, magnitude: 2 },
+        DirecVector { direction: Direction::Down, magnitude: 1 },
+    ];
+    assert!(graph.commit_path(&Vec2 { x: 0, y: 2 }, &touching));
+    assert!(graph.is_planar_embedding());
+    assert!(graph.are_connected(
+        Vec2 { x: 0, y: 2 }.as_ref(),
+        Vec2 { x: 2, y: 2 }.as_ref()
+    ));
+    assert!(graph.are_connected(
+        Vec2 { x: 2, y: 2 }.as_ref(),
+        Vec2 { x: 2, y: 3 }.as_ref()
+    ));
+
+    let mut free = before;
+    free.set_planarity(Planarity::Free);
+    assert!(free.commit_path(&Vec2 { x: 0, y: 2 }, &across));
+    assert_eq!(free.crossings().len(), 1);
+}
+
+/// Cost of stepping into the given point of a weighted 10x10 grid, if the
+/// point is inside the grid.
+fn grid_cost(weights: &[[i32; 10]; 10], point: &Vec2<i32>) -> Option<i32> {
+    if (0 .. 10).contains(&point.x) && (0 .. 10).contains(&point.y) {
+        Some(weights[point.y as usize][point.x as usize])
+    } else {
+        None
+    }
+}
+
+#[test]
+fn a_star_weighted() {
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0 .. 20 {
+        let mut weights = [[0; 10]; 10];
+        for row in weights.iter_mut() {
+            for weight in row.iter_mut() {
+                *weight = rng.gen_range(1 ..= 4);
+            }
+        }
+        let start =
+            Vec2 { x: rng.gen_range(0 .. 10), y: rng.gen_range(0 .. 10) };
+        let goal =
+            Vec2 { x: rng.gen_range(0 .. 10), y: rng.gen_range(0 .. 10) };
+
+        // Reference costs by relaxing every point until nothing changes.
+        let mut costs = BTreeMap::new();
+        costs.insert(start, 0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let points: Vec<_> =
+                costs.iter().map(|(&point, &cost)| (point, cost)).collect();
+            for (point, cost) in points {
+                for direction in Direction::iter() {
+                    let next = point.move_one(direction);
+                    if let Some(step) = grid_cost(&weights, &next) {
+                        let improves = match costs.get(&next) {
+                            Some(&best) => cost + step < best,
+                            None => true,
+                        };
+                        if improves {
+                            costs.insert(next, cost + step);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        for &penalty in &[0, 2] {
+            let mut graph = Graph::new();
+            graph.create_vertex(start);
+            graph.create_vertex(goal);
+            let path = graph
+                .find_path_weighted(&start, &goal, &penalty, &1, |_, to, _| {
+                    grid_cost(&weights, to)
+                })
+                .unwrap();
+            let expected = path
+                .points()
+                .skip(1)
+                .map(|point| grid_cost(&weights, &point).unwrap())
+                .sum::<i32>()
+                + penalty * path.turns() as i32;
+            assert_eq!(path.end(), goal);
+            assert_eq!(path.cost, expected);
+            let cost = path.cost;
+            if penalty == 0 {
+                assert_eq!(cost, costs[&goal]);
+            } else {
+                assert!(cost >= costs[&goal]);
+            }
+
+            let made = graph.make_path_weighted(
+                &start,
+                &goal,
+                &penalty,
+                &1,
+                |_, to, _| grid_cost(&weights, to),
+            );
+            assert_eq!(made, Some(path.steps));
+        }
+    }
+}
+
+#[test]
+fn a_star_weighted_unit() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut graph = Graph::with_planarity(Planarity::Forbid);
+    let mut vertices = Vec::new();
+    for _ in 0 .. 10 {
+        let vertex =
+            Vec2 { x: rng.gen_range(0 .. 14), y: rng.gen_range(0 .. 14) };
+        graph.create_vertex(vertex);
+        vertices.push(vertex);
+    }
+    let valid_points = |point: &Vec2<i32>| {
+        (0 .. 14).contains(&point.x) && (0 .. 14).contains(&point.y)
+    };
+    for _ in 0 .. 20 {
+        let start = vertices[rng.gen_range(0 .. vertices.len())];
+        let goal = vertices[rng.gen_range(0 .. vertices.len())];
+        let mut weighted = graph.clone();
+        let made = graph.make_path(&start, &goal, &2, valid_points);
+        let made_weighted =
+            weighted.make_path_weighted(&start, &goal, &2, &1, |_, to, _| {
+                if valid_points(to) {
+                    Some(1)
+                } else {
+                    None
+                }
+            });
+        assert_eq!(made, made_weighted);
+        assert_eq!(graph, weighted);
+    }
+}
+
+#[test]
+fn a_star_multi() {
+    let mut rng = StdRng::seed_from_u64(13);
+    let mut buf = PathMakerBuf::new();
+    for _ in 0 .. 20 {
+        let walls: BTreeSet<_> = (0 .. 30)
+            .map(|_| Vec2 {
+                x: rng.gen_range(0 .. 12),
+                y: rng.gen_range(0 .. 12),
+            })
+            .collect();
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 12).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let mut random_points = |count: usize| -> Vec<Vec2<i32>> {
+            (0 .. count)
+                .map(|_| Vec2 {
+                    x: rng.gen_range(0 .. 12),
+                    y: rng.gen_range(0 .. 12),
+                })
+                .filter(|point| valid_points(point))
+                .collect()
+        };
+        let starts = random_points(3);
+        let goals = random_points(3);
+
+        let mut graph = Graph::new();
+        graph.extend_vertices(starts.iter().chain(&goals).copied());
+        let expected = starts
+            .iter()
+            .flat_map(|start| goals.iter().map(move |goal| (start, goal)))
+            .filter_map(|(start, goal)| {
+                graph.find_path(start, goal, &0, valid_points)
+            })
+            .map(|path| path.cost)
+            .min();
+
+        let mut multi = graph.clone();
+        let found =
+            buf.make_path_multi(&mut multi, &starts, &goals, &0, valid_points);
+        let mut to = graph.clone();
+        let found_to = buf.make_path_to(
+            &mut to,
+            &starts,
+            |point| goals.contains(point),
+            &0,
+            valid_points,
+        );
+        match (found, found_to) {
+            (Some(found), Some(found_to)) => {
+                for path in &[&found, &found_to] {
+                    assert!(starts.contains(&path.start));
+                    assert!(goals.contains(&path.end()));
+                    assert_eq!(Some(path.length()), expected);
+                    assert_eq!(Some(path.cost), expected);
+                }
+                let mut committed = graph.clone();
+                committed.commit_path(&found.start, &found.steps);
+                assert_eq!(committed, multi);
+            },
+            (None, None) => assert_eq!(expected, None),
+            (found, found_to) => panic!("{:?} vs {:?}", found, found_to),
+        }
+    }
+
+    let mut graph = Graph::<i32>::new();
+    let start = Vec2 { x: 0, y: 0 };
+    assert_eq!(graph.make_path_multi(&[start], &[], &2, |_| true), None);
+    assert_eq!(graph.make_path_multi(&[], &[start], &2, |_| true), None);
+    assert_eq!(
+        graph.make_path_multi(&[start], &[start], &2, |_| true),
+        Some(Path::new(start, vec![], 0))
+    );
+}
+
+#[test]
+fn path_search_steps() {
+    let mut rng = StdRng::seed_from_u64(17);
+    let mut buf = PathMakerBuf::new();
+    for round in 0 .. 20 {
+        let walls: BTreeSet<_> = (0 .. 40)
+            .map(|_| Vec2 {
+                x: rng.gen_range(0 .. 12),
+                y: rng.gen_range(0 .. 12),
+            })
+            .collect();
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 12).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let start = Vec2 { x: 0, y: rng.gen_range(0 .. 12) };
+        let goal = Vec2 { x: 11, y: rng.gen_range(0 .. 12) };
+        let mut graph = Graph::new();
+        graph.create_vertex(start);
+        graph.create_vertex(goal);
+
+        let expected = graph.find_path(&start, &goal, &2, valid_points);
+        let mut search =
+            buf.path_search(&graph, &start, &goal, &2, valid_points);
+        let status = loop {
+            match search.step(round % 4 + 1) {
+                SearchStatus::Pending => (),
+                status => break status,
+            }
+        };
+        assert!(!search.limit_reached());
+        assert_eq!(search.step(1), status);
+        match expected {
+            Some(path) => {
+                let cost = path.cost;
+                assert_eq!(status, SearchStatus::Found(path.clone()));
+                assert_eq!(search.best_partial(), Some(path));
+
+                let mut search = buf
+                    .path_search(&graph, &start, &goal, &2, valid_points)
+                    .max_cost(cost - 1);
+                assert_eq!(search.step(usize::MAX), SearchStatus::NoPath);
+                assert!(search.limit_reached());
+                let partial = search.best_partial().unwrap();
+                assert_eq!(partial.start, start);
+                assert!(partial.cost < cost);
+                assert!(partial
+                    .points()
+                    .skip(1)
+                    .all(|point| valid_points(&point)));
+
+                let mut search = buf
+                    .path_search(&graph, &start, &goal, &2, valid_points)
+                    .max_cost(cost);
+                assert!(matches!(
+                    search.step(usize::MAX),
+                    SearchStatus::Found(found) if found.cost == cost
+                ));
+            },
+            None => assert_eq!(status, SearchStatus::NoPath),
+        }
+
+        // An abandoned search does not disturb the next one.
+        let mut search =
+            buf.path_search(&graph, &start, &goal, &2, valid_points);
+        search.step(7);
+        assert_eq!(
+            buf.find_path(&graph, &start, &goal, &2, valid_points),
+            graph.find_path(&start, &goal, &2, valid_points)
+        );
+    }
+}
+
+/// Cost of the cheapest route between two vertices, by relaxing every state
+/// (a vertex and the direction it was reached from) until nothing changes.
+fn route_cost(
+    graph: &Graph<i32>,
+    start: Vec2<i32>,
+    goal: Vec2<i32>,
+    penalty: i32,
+) -> Option<i32> {
+    let mut costs = BTreeMap::new();
+    costs.insert((start, None), 0);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let states: Vec<_> =
+            costs.iter().map(|(&state, &cost)| (state, cost)).collect();
+        for ((vertex, arrival), cost) in states {
+            for direction in Direction::iter() {
+                if let Some(neighbour) =
+                    graph.connected_at(vertex.as_ref(), direction)
+                {
+                    let neighbour = neighbour.copied();
+                    let mut attempt = cost + vertex.manhattan_dist(neighbour);
+                    if matches!(arrival, Some(prev) if prev != direction) {
+                        attempt += penalty;
+                    }
+                    let state = (neighbour, Some(direction));
+                    let improves = match costs.get(&state) {
+                        Some(&best) => attempt < best,
+                        None => true,
+                    };
+                    if improves {
+                        costs.insert(state, attempt);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+    costs
+        .into_iter()
+        .filter(|((vertex, _), _)| *vertex == goal)
+        .map(|(_, cost)| cost)
+        .min()
+}
+
+#[test]
+fn shortest_route() {
+    for seed in 0 .. 10 {
+        let graph = random_graph(seed);
+        let vertices: Vec<_> = graph
+            .vertices_edges()
+            .rows()
+            .map(|(vertex, _)| vertex.copied())
+            .collect();
+        for &start in vertices.iter().step_by(3) {
+            for &goal in vertices.iter().step_by(4) {
+                for &penalty in &[0, 2] {
+                    let expected = route_cost(&graph, start, goal, penalty);
+                    let route = graph.shortest_route(&start, &goal, &penalty);
+                    let (route_vertices, steps) = match route {
+                        Some(route) => (route.vertices, route.steps),
+                        None => {
+                            assert_eq!(expected, None);
+                            continue;
+                        },
+                    };
+                    assert_eq!(route_vertices.first(), Some(&start));
+                    assert_eq!(route_vertices.last(), Some(&goal));
+                    for pair in route_vertices.windows(2) {
+                        assert!(graph
+                            .are_connected(pair[0].as_ref(), pair[1].as_ref()));
+                    }
+                    let mut point = start;
+                    for step in &steps {
+                        point = point.move_by(*step);
+                    }
+                    assert_eq!(point, goal);
+                    let length: i32 =
+                        steps.iter().map(|step| step.magnitude).sum();
+                    let turns = steps.len().saturating_sub(1) as i32;
+                    assert_eq!(Some(length + penalty * turns), expected);
+                }
+            }
+        }
+    }
+
+    let mut graph = Graph::<i32>::new();
+    let start = Vec2 { x: 0, y: 0 };
+    graph.create_vertex(start);
+    assert_eq!(
+        graph.shortest_route(&start, &start, &2),
+        Some(Route { vertices: vec![start], steps: vec![] })
+    );
+    assert_eq!(graph.shortest_route(&start, &Vec2 { x: 1, y: 0 }, &2), None);
+}
+
+/// Points covered by the edges of the graph.
+fn edge_points(graph: &Graph<i32>) -> BTreeSet<Vec2<i32>> {
+    graph.segments().flat_map(|segment| segment.points()).collect()
+}
+
+#[test]
+fn simplify() {
+    for seed in 0 .. 20 {
+        let mut graph = random_graph(seed);
+        let points = edge_points(&graph);
+        let vertices = graph.vertices_edges().len();
+        let redundant = graph
+            .vertices_edges()
+            .rows()
+            .filter(|(vertex, _)| graph.is_redundant(*vertex))
+            .count();
+        assert_eq!(graph.simplify(), redundant);
+        assert_eq!(graph.vertices_edges().len(), vertices - redundant);
+        assert_consistent(&graph);
+        assert_eq!(edge_points(&graph), points);
+        assert!(graph
+            .vertices_edges()
+            .rows()
+            .all(|(vertex, _)| !graph.is_redundant(vertex)));
+        assert_eq!(graph.simplify(), 0);
+    }
+
+    let mut graph = Graph::<i32>::new();
+    assert!(!graph.is_redundant(Vec2 { x: 0, y: 0 }.as_ref()));
+    graph.create_vertex(Vec2 { x: 0, y: 0 });
+    assert!(!graph.is_redundant(Vec2 { x: 0, y: 0 }.as_ref()));
+}
+
+#[test]
+fn densify() {
+    for seed in 0 .. 20 {
+        let mut graph = random_graph(seed);
+        graph.simplify();
+        let simplified = graph.clone();
+        let points = edge_points(&graph);
+        graph.densify(0);
+        assert_eq!(graph, simplified);
+        graph.densify(3);
+        assert_consistent(&graph);
+        assert_eq!(edge_points(&graph), points);
+        // Only crossing points are skipped.
+        let crossings: BTreeSet<_> = graph
+            .crossings()
+            .into_iter()
+            .map(|crossing| crossing.point.copied())
+            .collect();
+        for segment in graph.segments() {
+            if segment.vector.magnitude > 3 {
+                assert!(crossings.contains(&segment.points().nth(3).unwrap()));
+            }
+        }
+        graph.simplify();
+        assert_eq!(graph, simplified);
+    }
+
+    let mut graph = Graph::<u8>::new();
+    graph.create_vertex(Vec2 { x: 0, y: 0 });
+    graph.create_vertex(Vec2 { x: 255, y: 0 });
+    graph.connect(Vec2 { x: 0, y: 0 }.as_ref(), Vec2 { x: 255, y: 0 }.as_ref());
+    graph.densify(100);
+    let vertices: Vec<_> =
+        graph.vertices_edges().rows().map(|(vertex, _)| *vertex.x).collect();
+    assert_eq!(vertices, vec![0, 100, 200, 255]);
+}
+
+fn assert_valid_path<F>(path: &Path<i32>, mut valid_points: F)
+where
+    F: FnMut(&Vec2<i32>) -> bool,
+{
+    assert_eq!(path.cost, path.length());
+    for point in path.points().skip(1) {
+        assert!(valid_points(&point));
+    }
+}
+
+#[test]
+fn jump_point_search() {
+    let mut rng = StdRng::seed_from_u64(21);
+    let graph = Graph::new();
+    let mut a_star = PathMakerBuf::new();
+    let mut jumping = PathMakerBuf::with_options(SearchOptions {
+        strategy: SearchStrategy::JumpPoint,
+        max_jump: None,
+    });
+    for round in 0 .. 200 {
+        let walls: BTreeSet<_> = (0 .. round % 60)
+            .map(|_| Vec2 {
+                x: rng.gen_range(0 .. 12),
+                y: rng.gen_range(0 .. 12),
+            })
+            .collect();
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 12).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let start =
+            Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+        let goal =
+            Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+
+        let expected =
+            a_star.find_path(&graph, &start, &goal, &0, valid_points);
+        let found = jumping.find_path(&graph, &start, &goal, &0, valid_points);
+        match (&expected, &found) {
+            (Some(expected), Some(found)) => {
+                assert_eq!(found.start, start);
+                assert_eq!(found.end(), goal);
+                assert_eq!(found.length(), expected.length());
+                assert_valid_path(found, valid_points);
+            },
+            (None, None) => (),
+            _ => panic!("{:?} != {:?}", expected, found),
+        }
+
+        let is_goal = |point: &Vec2<i32>| point.x == 11 && point.y % 4 == 0;
+        let mut expected_graph = graph.clone();
+        let mut found_graph = graph.clone();
+        let expected = a_star.make_path_to(
+            &mut expected_graph,
+            Some(&start),
+            is_goal,
+            &0,
+            valid_points,
+        );
+        let found = jumping.make_path_to(
+            &mut found_graph,
+            Some(&start),
+            is_goal,
+            &0,
+            valid_points,
+        );
+        match (&expected, &found) {
+            (Some(expected), Some(found)) => {
+                assert!(is_goal(&found.end()));
+                assert_eq!(found.length(), expected.length());
+                assert_valid_path(found, valid_points);
+            },
+            (None, None) => (),
+            _ => panic!("{:?} != {:?}", expected, found),
+        }
+    }
+}
+
+#[test]
+fn jump_point_search_open() {
+    let graph = Graph::new();
+    let start = Vec2 { x: 3, y: 5 };
+    let goal = Vec2 { x: 90, y: 70 };
+    let valid_points = |point: &Vec2<i32>| {
+        (0 .. 100).contains(&point.x)
+            && (0 .. 100).contains(&point.y)
+            && (point.x != 50 || point.y > 80)
+    };
+    let mut a_star = PathMakerBuf::new();
+    let expected =
+        a_star.find_path(&graph, &start, &goal, &0, valid_points).unwrap();
+    let mut jumping = PathMakerBuf::with_options(SearchOptions {
+        strategy: SearchStrategy::JumpPoint,
+        max_jump: None,
+    });
+    let found =
+        jumping.find_path(&graph, &start, &goal, &0, valid_points).unwrap();
+    assert_eq!(found.length(), expected.length());
+    assert_valid_path(&found, valid_points);
+    assert!(jumping.expansions() * 10 < a_star.expansions());
+
+    // Unbounded maps need a limit on jumps.
+    jumping
+        .set_options(SearchOptions { max_jump: Some(16), ..jumping.options() });
+    let found = jumping.find_path(&graph, &start, &goal, &0, |_| true).unwrap();
+    assert_eq!(found.length(), 87 + 65);
+
+    // Turning penalties fall back to A*.
+    let expected =
+        a_star.find_path(&graph, &start, &goal, &2, valid_points).unwrap();
+    let found =
+        jumping.find_path(&graph, &start, &goal, &2, valid_points).unwrap();
+    assert_eq!(found, expected);
+    assert_eq!(jumping.expansions(), a_star.expansions());
+}
+
+#[test]
+fn deterministic_ties() {
+    let mut rng = StdRng::seed_from_u64(34);
+    let mut fixed = PathMakerBuf::with_hasher(BuildHasherDefault::<
+        DefaultHasher,
+    >::default());
+    let mut random = PathMakerBuf::new();
+    for round in 0 .. 100 {
+        let walls: BTreeSet<_> = (0 .. round % 40)
+            .map(|_| Vec2 {
+                x: rng.gen_range(0 .. 12),
+                y: rng.gen_range(0 .. 12),
+            })
+            .collect();
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 12).contains(&point.x)
+                && (0 .. 12).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let start =
+            Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+        let goal =
+            Vec2 { x: rng.gen_range(0 .. 12), y: rng.gen_range(0 .. 12) };
+        let strategy = if round % 2 == 0 {
+            SearchStrategy::AStar
+        } else {
+            SearchStrategy::JumpPoint
+        };
+        let options = SearchOptions { strategy, max_jump: None };
+        fixed.set_options(options);
+        random.set_options(options);
+        for penalty in 0 .. 3 {
+            let expected = fixed.find_path(
+                &Graph::new(),
+                &start,
+                &goal,
+                &penalty,
+                valid_points,
+            );
+            for _ in 0 .. 3 {
+                let mut other = PathMakerBuf::with_options(options);
+                assert_eq!(
+                    random.find_path(
+                        &Graph::new(),
+                        &start,
+                        &goal,
+                        &penalty,
+                        valid_points
+                    ),
+                    expected
+                );
+                assert_eq!(
+                    other.find_path(
+                        &Graph::new(),
+                        &start,
+                        &goal,
+                        &penalty,
+                        valid_points
+                    ),
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! This module provides hierarchical path finding (HPA*) for large maps.
+//!
+//! The map is divided into clusters, rectangles of the same size. Wherever
+//! two neighbouring clusters can be crossed, an entrance is placed on each
+//! side of their border, and the entrances of a cluster are linked to each
+//! other by the shortest paths inside the cluster. These entrances and paths
+//! are stored in a [`Graph`], the abstraction of the map. A query searches the
+//! abstraction, and only the clusters of the start and the goal are searched
+//! point by point, to join the start and the goal to the abstraction. Paths
+//! found this way are not always the shortest ones, but they are close to it,
+//! and their cost does not grow with the area of the map.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    axis::Axis,
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+    graph::{Graph, PathMakerBuf, SearchOptions, SearchStrategy},
+    path::Path,
+    rect::Rect,
+    segment::Segment,
+};
+use num::{CheckedAdd, CheckedSub, One, Zero};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    iter,
+    ops::{Add, AddAssign, Div, Mul, Sub},
+};
+
+/// The abstraction of a map for hierarchical path finding, made of clusters
+/// whose entrances are linked in a graph.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{coord::Vec2, hierarchy::PathHierarchy, rect::Rect};
+///
+/// # fn main() {
+/// // A wall with a single door splits the map in two.
+/// let mut door = Vec2 { x: 20, y: 20 };
+/// let valid_points = |door: Vec2<u16>| {
+///     move |point: &Vec2<u16>| point.y != 20 || *point == door
+/// };
+///
+/// let bounds =
+///     Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 40, y: 40 } };
+/// let cluster_size = Vec2 { x: 8, y: 8 };
+/// let mut hierarchy =
+///     PathHierarchy::new(bounds, cluster_size, valid_points(door));
+///
+/// let start = Vec2 { x: 3, y: 2 };
+/// let goal = Vec2 { x: 3, y: 37 };
+/// let path =
+///     hierarchy.find_path(&start, &goal, valid_points(door)).unwrap();
+/// assert_eq!(path.end(), goal);
+/// assert!(path.points().any(|point| point == door));
+///
+/// // The door moves: only its old and new clusters are rebuilt.
+/// let old_door = door;
+/// door = Vec2 { x: 35, y: 20 };
+/// hierarchy.rebuild_cluster(&old_door, valid_points(door));
+/// hierarchy.rebuild_cluster(&door, valid_points(door));
+/// let path =
+///     hierarchy.find_path(&start, &goal, valid_points(door)).unwrap();
+/// assert!(path.points().any(|point| point == door));
+/// assert!(!path.points().any(|point| point == old_door));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct PathHierarchy<T>
+where
+    T: Ord,
+{
+    bounds: Rect<T>,
+    cluster_size: Vec2<T>,
+    #[cfg_attr(
+        feature = "impl-serde",
+        serde(bound(deserialize = "T: serde::Deserialize<'de> + Clone"))
+    )]
+    graph: Graph<T>,
+}
+
+impl<T> PathHierarchy<T>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+    for<'points> T: AddAssign<&'points T>,
+{
+    /// Builds the abstraction of the map inside of the given bounds, divided
+    /// into clusters of the given size (clusters at the end of the bounds may
+    /// be smaller). The only points used by paths are the ones validated by
+    /// the given function `valid_points`. The end of the bounds is assumed to
+    /// be representable by `T`.
+    ///
+    /// # Panics
+    /// Panics if the size of the clusters is zero in some axis.
+    pub fn new<F>(
+        bounds: Rect<T>,
+        cluster_size: Vec2<T>,
+        valid_points: F,
+    ) -> Self
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        assert!(
+            Axis::iter().all(|axis| !cluster_size[axis].is_zero()),
+            "Cluster size must not be zero"
+        );
+        let mut this = Self { bounds, cluster_size, graph: Graph::new() };
+        let count = this.cluster_count();
+        let clusters: Vec<_> = range(T::zero(), count.y.clone())
+            .flat_map(|y| {
+                range(T::zero(), count.x.clone())
+                    .map(move |x| Vec2 { x, y: y.clone() })
+            })
+            .collect();
+        this.build(&clusters, valid_points);
+        this
+    }
+
+    /// The bounds of the map.
+    pub fn bounds(&self) -> &Rect<T> {
+        &self.bounds
+    }
+
+    /// The size of the clusters.
+    pub fn cluster_size(&self) -> &Vec2<T> {
+        &self.cluster_size
+    }
+
+    /// The abstraction of the map. Its vertices are the entrances of the
+    /// clusters, together with the corners of the paths linking them, and
+    /// each edge is a straight run of valid points.
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// The cluster containing the given point, or `None` if the point is out
+    /// of the bounds.
+    pub fn cluster_at(&self, point: &Vec2<T>) -> Option<Rect<T>> {
+        self.cluster_index(point).map(|index| self.cluster_rect(&index))
+    }
+
+    /// Rebuilds the abstraction of the cluster containing the given point,
+    /// after the points of the cluster changed, without rebuilding the whole
+    /// map. The neighbouring clusters are rebuilt too, since their entrances
+    /// depend on the shared borders. Returns whether the point is inside the
+    /// bounds.
+    pub fn rebuild_cluster<F>(
+        &mut self,
+        point: &Vec2<T>,
+        valid_points: F,
+    ) -> bool
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let index = match self.cluster_index(point) {
+            Some(index) => index,
+            None => return false,
+        };
+        let mut clusters = vec![index.clone()];
+        clusters.extend(
+            Direction::iter().filter_map(|direction| {
+                self.neighbour_index(&index, direction)
+            }),
+        );
+        self.build(&clusters, valid_points);
+        true
+    }
+
+    /// Finds a path from the given starting point till the "goal" point,
+    /// through the abstraction of the map. The path is then refined inside
+    /// of the clusters of the start and the goal. The only points used are
+    /// the ones validated by `valid_points`, which must be the same as the
+    /// one the abstraction was built with; unlike [`Graph::find_path`], the
+    /// start must be valid too. The cost of the path is its length. Returns
+    /// `None` if there is no path, or if the start or the goal are invalid or
+    /// out of the bounds.
+    pub fn find_path<F>(
+        &self,
+        start: &Vec2<T>,
+        goal: &Vec2<T>,
+        mut valid_points: F,
+    ) -> Option<Path<T>>
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let start_cluster = self.cluster_at(start)?;
+        let goal_cluster = self.cluster_at(goal)?;
+        if !valid_points(start) || !valid_points(goal) {
+            return None;
+        }
+        let from_start = distances(start, &start_cluster, &mut valid_points);
+        let to_goal = distances(goal, &goal_cluster, &mut valid_points);
+
+        let mut travelled = HashMap::new();
+        let mut predecessors = HashMap::new();
+        let mut cost_nodes = BinaryHeap::new();
+        if let Some(distance) = from_start.get(goal) {
+            let node = Node::Goal(None);
+            cost_nodes.push(Reverse((
+                distance.clone(),
+                distance.clone(),
+                node,
+            )));
+        }
+        for vertex in self.vertices_in(&start_cluster) {
+            if let Some(distance) = from_start.get(&vertex) {
+                travelled.insert(vertex.clone(), distance.clone());
+                let estimate = distance.clone()
+                    + vertex.clone().manhattan_dist(goal.clone());
+                let node = Node::Vertex(vertex);
+                cost_nodes.push(Reverse((estimate, distance.clone(), node)));
+            }
+        }
+
+        let (cost, last) = loop {
+            let Reverse((_, cost, node)) = cost_nodes.pop()?;
+            let vertex = match node {
+                Node::Goal(last) => break (cost, last),
+                Node::Vertex(vertex) => vertex,
+            };
+            if matches!(travelled.get(&vertex), Some(best) if *best < cost) {
+                continue;
+            }
+
+            if let Some(distance) = to_goal.get(&vertex) {
+                let total = cost.clone() + distance.clone();
+                let node = Node::Goal(Some(vertex.clone()));
+                cost_nodes.push(Reverse((total.clone(), total, node)));
+            }
+
+            for direction in Direction::iter() {
+                let neighbour =
+                    match self.graph.connected_at(vertex.as_ref(), direction) {
+                        Some(neighbour) => neighbour.cloned(),
+                        None => continue,
+                    };
+                let attempt = cost.clone()
+                    + vertex.clone().manhattan_dist(neighbour.clone());
+                let is_better = match travelled.get(&neighbour) {
+                    Some(best) => attempt < *best,
+                    None => true,
+                };
+                if is_better {
+                    travelled.insert(neighbour.clone(), attempt.clone());
+                    predecessors.insert(neighbour.clone(), vertex.clone());
+                    let estimate = attempt.clone()
+                        + neighbour.clone().manhattan_dist(goal.clone());
+                    let node = Node::Vertex(neighbour);
+                    cost_nodes.push(Reverse((estimate, attempt, node)));
+                }
+            }
+        };
+
+        // Refines the path inside of the clusters of the start and the goal.
+        let mut buf = PathMakerBuf::with_options(SearchOptions {
+            strategy: SearchStrategy::JumpPoint,
+            max_jump: None,
+        });
+        let zero = T::zero();
+        let mut local_path =
+            |from: &Vec2<T>, to: &Vec2<T>, cluster: &Rect<T>| {
+                buf.find_path(&self.graph, from, to, &zero, |point| {
+                    rect_contains(cluster, point) && valid_points(point)
+                })
+            };
+
+        let mut steps = Vec::new();
+        match last {
+            None => {
+                let path = local_path(start, goal, &start_cluster)?;
+                extend_steps(&mut steps, path.steps);
+            },
+            Some(last) => {
+                let mut vertices = vec![last.clone()];
+                let mut current = &last;
+                while let Some(prev) = predecessors.get(current) {
+                    vertices.push(prev.clone());
+                    current = prev;
+                }
+                vertices.reverse();
+
+                let head = local_path(start, &vertices[0], &start_cluster)?;
+                extend_steps(&mut steps, head.steps);
+                extend_steps(
+                    &mut steps,
+                    vertices.windows(2).map(|pair| {
+                        let (from, to) = (pair[0].clone(), pair[1].clone());
+                        Segment::from_ends(from, to).unwrap().vector
+                    }),
+                );
+                let tail = local_path(&last, goal, &goal_cluster)?;
+                extend_steps(&mut steps, tail.steps);
+            },
+        }
+
+        Some(Path::new(start.clone(), steps, cost))
+    }
+
+    /// Rebuilds the abstraction of the given clusters: their vertices are
+    /// removed, then their entrances are placed again and linked.
+    fn build<F>(&mut self, clusters: &[Vec2<T>], mut valid_points: F)
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        for index in clusters {
+            for vertex in self.vertices_in(&self.cluster_rect(index)) {
+                for direction in Direction::iter() {
+                    let neighbour = self
+                        .graph
+                        .connected_at(vertex.as_ref(), direction)
+                        .map(|neighbour| neighbour.cloned());
+                    if let Some(neighbour) = neighbour {
+                        self.graph
+                            .disconnect(vertex.as_ref(), neighbour.as_ref());
+                    }
+                }
+                self.graph.remove_vertex(vertex.as_ref());
+            }
+        }
+
+        for index in clusters {
+            let cluster = self.cluster_rect(index);
+            for direction in Direction::iter() {
+                if self.neighbour_index(index, direction).is_none() {
+                    continue;
+                }
+                let entrances =
+                    entrances(&cluster, direction, &mut valid_points);
+                for (inner, outer) in entrances {
+                    self.graph.create_vertex(inner.clone());
+                    self.graph.create_vertex(outer.clone());
+                    self.graph.connect(inner.as_ref(), outer.as_ref());
+                }
+            }
+        }
+
+        let mut buf = PathMakerBuf::with_options(SearchOptions {
+            strategy: SearchStrategy::JumpPoint,
+            max_jump: None,
+        });
+        let zero = T::zero();
+        for index in clusters {
+            let cluster = self.cluster_rect(index);
+            let entrances = self.vertices_in(&cluster);
+            for (i, from) in entrances.iter().enumerate() {
+                for to in &entrances[i + 1 ..] {
+                    let path =
+                        buf.find_path(&self.graph, from, to, &zero, |point| {
+                            rect_contains(&cluster, point)
+                                && valid_points(point)
+                        });
+                    if let Some(path) = path {
+                        self.graph.commit_path(&path.start, &path.steps);
+                    }
+                }
+            }
+        }
+    }
+
+    /// How many clusters there are in each axis.
+    fn cluster_count(&self) -> Vec2<T> {
+        Vec2::from_axes(|axis| {
+            let size = self.bounds.size[axis].clone();
+            let cluster_size = self.cluster_size[axis].clone();
+            let count = size.clone() / cluster_size.clone();
+            if count.clone() * cluster_size < size {
+                count + T::one()
+            } else {
+                count
+            }
+        })
+    }
+
+    /// The index of the cluster containing the given point, if inside the
+    /// bounds.
+    fn cluster_index(&self, point: &Vec2<T>) -> Option<Vec2<T>> {
+        if !rect_contains(&self.bounds, point) {
+            return None;
+        }
+        Some(Vec2::from_axes(|axis| {
+            (point[axis].clone() - self.bounds.start[axis].clone())
+                / self.cluster_size[axis].clone()
+        }))
+    }
+
+    /// The index of the neighbour of the given cluster in the given
+    /// direction, if there is such a cluster.
+    fn neighbour_index(
+        &self,
+        index: &Vec2<T>,
+        direction: Direction,
+    ) -> Option<Vec2<T>> {
+        let neighbour = index.clone().checked_move(direction)?;
+        let count = self.cluster_count();
+        let is_inside = Axis::iter().all(|axis| {
+            neighbour[axis] >= T::zero() && neighbour[axis] < count[axis]
+        });
+        if is_inside {
+            Some(neighbour)
+        } else {
+            None
+        }
+    }
+
+    /// The rectangle of the cluster with the given index.
+    fn cluster_rect(&self, index: &Vec2<T>) -> Rect<T> {
+        let offset = Vec2::from_axes(|axis| {
+            index[axis].clone() * self.cluster_size[axis].clone()
+        });
+        Rect {
+            start: Vec2::from_axes(|axis| {
+                self.bounds.start[axis].clone() + offset[axis].clone()
+            }),
+            size: Vec2::from_axes(|axis| {
+                let remaining =
+                    self.bounds.size[axis].clone() - offset[axis].clone();
+                remaining.min(self.cluster_size[axis].clone())
+            }),
+        }
+    }
+
+    /// The vertices of the abstraction inside of the given cluster, row by
+    /// row.
+    fn vertices_in(&self, cluster: &Rect<T>) -> Vec<Vec2<T>> {
+        let end_x = cluster.start.x.clone() + cluster.size.x.clone();
+        let end_y = cluster.start.y.clone() + cluster.size.y.clone();
+        let mut vertices = Vec::new();
+        for y in range(cluster.start.y.clone(), end_y) {
+            let row = Vec2 { x: &cluster.start.x, y: &y };
+            vertices.extend(
+                self.graph
+                    .vertices_edges()
+                    .neighbours_from_incl(row, Direction::Right)
+                    .map(|(vertex, _)| vertex)
+                    .take_while(|vertex| *vertex.x < end_x)
+                    .map(Vec2::cloned),
+            );
+        }
+        vertices
+    }
+}
+
+/// A node of the search in the abstraction: either a vertex, or the goal,
+/// reached from the given last vertex, or directly from the start.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Node<T> {
+    Vertex(Vec2<T>),
+    Goal(Option<Vec2<T>>),
+}
+
+/// Iterator over the numbers from `start` (included) till `end` (excluded).
+fn range<T>(start: T, end: T) -> impl Iterator<Item = T>
+where
+    T: Clone + Ord + Add<Output = T> + One,
+{
+    iter::successors(Some(start), |current| Some(current.clone() + T::one()))
+        .take_while(move |current| *current < end)
+}
+
+/// Tests whether the given rectangle contains the given point.
+fn rect_contains<T>(rect: &Rect<T>, point: &Vec2<T>) -> bool
+where
+    T: Clone + Ord + Sub<Output = T>,
+{
+    Axis::iter().all(|axis| {
+        rect.start[axis] <= point[axis]
+            && point[axis].clone() - rect.start[axis].clone() < rect.size[axis]
+    })
+}
+
+/// Computes the distances from the given source to every point reachable
+/// inside of the given cluster.
+fn distances<T, F>(
+    source: &Vec2<T>,
+    cluster: &Rect<T>,
+    valid_points: &mut F,
+) -> HashMap<Vec2<T>, T>
+where
+    T: Clone + Hash + Ord + Zero + One + Sub<Output = T>,
+    T: CheckedAdd + CheckedSub,
+    F: FnMut(&Vec2<T>) -> bool,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(source.clone(), T::zero());
+    queue.push_back(source.clone());
+    while let Some(point) = queue.pop_front() {
+        let distance = distances[&point].clone() + T::one();
+        for direction in Direction::iter() {
+            let neighbour = match point.clone().checked_move(direction) {
+                Some(neighbour) => neighbour,
+                None => continue,
+            };
+            if rect_contains(cluster, &neighbour)
+                && !distances.contains_key(&neighbour)
+                && valid_points(&neighbour)
+            {
+                distances.insert(neighbour.clone(), distance.clone());
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    distances
+}
+
+/// Finds the entrances on the border of the given cluster in the given
+/// direction. Each maximal run of valid points whose neighbours across the
+/// border are valid too gets one entrance, in its middle, given by the point
+/// inside of the cluster and the point across the border.
+fn entrances<T, F>(
+    cluster: &Rect<T>,
+    direction: Direction,
+    valid_points: &mut F,
+) -> Vec<(Vec2<T>, Vec2<T>)>
+where
+    T: Clone + Ord + Zero + One + Add<Output = T> + Sub<Output = T>,
+    F: FnMut(&Vec2<T>) -> bool,
+{
+    let axis = direction.axis();
+    let border = match direction {
+        Direction::Up | Direction::Left => cluster.start[axis].clone(),
+        Direction::Down | Direction::Right => {
+            cluster.start[axis].clone() + cluster.size[axis].clone() - T::one()
+        },
+    };
+    let other_start = cluster.start[!axis].clone();
+    let other_end = other_start.clone() + cluster.size[!axis].clone();
+
+    let mut entrances = Vec::new();
+    let mut run = Vec::new();
+    let mut end_run = |run: &mut Vec<Vec2<T>>| {
+        if !run.is_empty() {
+            let inner: Vec2<T> = run[run.len() / 2].clone();
+            entrances.push((inner.clone(), inner.move_one(direction)));
+            run.clear();
+        }
+    };
+    for coord in range(other_start, other_end) {
+        let inner = Vec2::from_axes(|current| {
+            if current == axis {
+                border.clone()
+            } else {
+                coord.clone()
+            }
+        });
+        let outer = inner.clone().move_one(direction);
+        if valid_points(&inner) && valid_points(&outer) {
+            run.push(inner);
+        } else {
+            end_run(&mut run);
+        }
+    }
+    end_run(&mut run);
+    entrances
+}
+
+/// Appends the given steps to a list of steps, merging steps in the same
+/// direction and skipping empty ones.
+fn extend_steps<T, I>(steps: &mut Vec<DirecVector<T>>, new_steps: I)
+where
+    T: AddAssign + Zero,
+    I: IntoIterator<Item = DirecVector<T>>,
+{
+    for step in new_steps {
+        if step.magnitude.is_zero() {
+            continue;
+        }
+        match steps.last_mut() {
+            Some(last) if last.direction == step.direction => {
+                last.magnitude += step.magnitude
+            },
+            _ => steps.push(step),
+        }
+    }
+}
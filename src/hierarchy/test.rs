@@ -0,0 +1,125 @@
+use super::PathHierarchy;
+use crate::{coord::Vec2, graph::Graph, path::Path, rect::Rect};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+
+fn random_walls(rng: &mut StdRng, count: usize) -> BTreeSet<Vec2<i32>> {
+    (0 .. count)
+        .map(|_| Vec2 { x: rng.gen_range(0 .. 30), y: rng.gen_range(0 .. 30) })
+        .collect()
+}
+
+fn assert_valid_path<F>(
+    path: &Path<i32>,
+    start: Vec2<i32>,
+    goal: Vec2<i32>,
+    mut valid_points: F,
+) where
+    F: FnMut(&Vec2<i32>) -> bool,
+{
+    assert_eq!(path.start, start);
+    assert_eq!(path.end(), goal);
+    assert_eq!(path.cost, path.length());
+    for point in path.points().skip(1) {
+        assert!(valid_points(&point));
+    }
+}
+
+#[test]
+fn find_path() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let bounds =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 30, y: 30 } };
+    for round in 0 .. 20 {
+        let walls = random_walls(&mut rng, 40 * (round % 8));
+        let valid_points = |point: &Vec2<i32>| {
+            (0 .. 30).contains(&point.x)
+                && (0 .. 30).contains(&point.y)
+                && !walls.contains(point)
+        };
+        let hierarchy =
+            PathHierarchy::new(bounds, Vec2 { x: 7, y: 6 }, valid_points);
+        let graph = Graph::new();
+        for _ in 0 .. 20 {
+            let start =
+                Vec2 { x: rng.gen_range(0 .. 30), y: rng.gen_range(0 .. 30) };
+            let goal =
+                Vec2 { x: rng.gen_range(0 .. 30), y: rng.gen_range(0 .. 30) };
+            let found = hierarchy.find_path(&start, &goal, valid_points);
+            if !valid_points(&start) {
+                assert_eq!(found, None);
+                continue;
+            }
+            let expected = graph.find_path(&start, &goal, &0, valid_points);
+            match (&expected, &found) {
+                (Some(expected), Some(found)) => {
+                    assert_valid_path(found, start, goal, valid_points);
+                    assert!(found.length() >= expected.length());
+                },
+                (None, None) => (),
+                _ => panic!("{:?} != {:?}", expected, found),
+            }
+        }
+    }
+}
+
+#[test]
+fn open_map_is_optimal() {
+    let bounds =
+        Rect { start: Vec2 { x: 0u16, y: 0 }, size: Vec2 { x: 50, y: 45 } };
+    let valid_points = |point: &Vec2<u16>| point.x < 50 && point.y < 45;
+    let hierarchy =
+        PathHierarchy::new(bounds, Vec2 { x: 10, y: 10 }, valid_points);
+    assert_eq!(
+        hierarchy.cluster_at(&Vec2 { x: 23, y: 44 }),
+        Some(Rect { start: Vec2 { x: 20, y: 40 }, size: Vec2 { x: 10, y: 5 } })
+    );
+    assert_eq!(hierarchy.cluster_at(&Vec2 { x: 23, y: 45 }), None);
+
+    let start = Vec2 { x: 2, y: 3 };
+    let goal = Vec2 { x: 47, y: 41 };
+    let path = hierarchy.find_path(&start, &goal, valid_points).unwrap();
+    assert_eq!(path.end(), goal);
+    assert_eq!(path.length(), 45 + 38);
+    assert_eq!(
+        hierarchy.find_path(&start, &Vec2 { x: 50, y: 0 }, valid_points),
+        None
+    );
+}
+
+#[test]
+fn rebuild_cluster() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let bounds =
+        Rect { start: Vec2 { x: 0, y: 0 }, size: Vec2 { x: 30, y: 30 } };
+    let cluster_size = Vec2 { x: 6, y: 8 };
+    let mut walls = random_walls(&mut rng, 150);
+    let mut hierarchy = PathHierarchy::new(bounds, cluster_size, |point| {
+        !walls.contains(point)
+    });
+    for _ in 0 .. 30 {
+        let cluster = hierarchy
+            .cluster_at(&Vec2 {
+                x: rng.gen_range(0 .. 30),
+                y: rng.gen_range(0 .. 30),
+            })
+            .unwrap();
+        for _ in 0 .. 10 {
+            let point = Vec2 {
+                x: cluster.start.x + rng.gen_range(0 .. cluster.size.x),
+                y: cluster.start.y + rng.gen_range(0 .. cluster.size.y),
+            };
+            if !walls.remove(&point) {
+                walls.insert(point);
+            }
+        }
+        assert!(hierarchy.rebuild_cluster(&cluster.start, |point| {
+            !walls.contains(point)
+        }));
+        let rebuilt = PathHierarchy::new(bounds, cluster_size, |point| {
+            !walls.contains(point)
+        });
+        assert_eq!(hierarchy, rebuilt);
+    }
+    assert!(!hierarchy.rebuild_cluster(&Vec2 { x: 30, y: 0 }, |_| true));
+}
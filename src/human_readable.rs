@@ -0,0 +1,59 @@
+//! Serde helpers that (de)serialize values through their string forms (i.e.
+//! [`fmt::Display`] and [`FromStr`]) when the format is human readable, such as
+//! configuration files. Non human readable formats keep using the regular
+//! serde implementations. Meant to be used with `#[serde(with = "...")]`.
+//!
+//! # Examples
+//! ```rust
+//! use gardiz::{coord::Vec2, direc::Direction, rect::Rect};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct Config {
+//!     // Written as "(x=3, y=5)" in human readable formats.
+//!     #[serde(with = "gardiz::human_readable")]
+//!     spawn: Vec2<i32>,
+//!     // Written as "(x=0, y=0)..(x=80, y=25)" in human readable formats.
+//!     #[serde(with = "gardiz::human_readable")]
+//!     arena: Rect<i32>,
+//!     // Written as "up" in human readable formats.
+//!     #[serde(with = "gardiz::human_readable")]
+//!     facing: Direction,
+//! }
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+/// Serializes the given value as a string if the serializer is human readable,
+/// or using its regular implementation otherwise.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display + Serialize,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(value)
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+/// Deserializes a value from a string if the deserializer is human readable,
+/// or using its regular implementation otherwise.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr + Deserialize<'de>,
+    T::Err: fmt::Display,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let input = String::deserialize(deserializer)?;
+        input.parse().map_err(de::Error::custom)
+    } else {
+        T::deserialize(deserializer)
+    }
+}
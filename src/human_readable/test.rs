@@ -0,0 +1,59 @@
+use super::{deserialize, serialize};
+use crate::{axis::Axis, coord::Vec2, direc::Direction, rect::Rect};
+use serde::{
+    de::{
+        value::{Error, StrDeserializer},
+        IntoDeserializer,
+    },
+    Serialize,
+};
+use std::fmt;
+
+fn deserializer(input: &str) -> StrDeserializer<'_, Error> {
+    input.into_deserializer()
+}
+
+/// Serializes through a formatter, which is a human readable serializer.
+struct Serialized<'value, T>(&'value T);
+
+impl<'value, T> fmt::Display for Serialized<'value, T>
+where
+    T: fmt::Display + Serialize,
+{
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        serialize(self.0, fmtr)
+    }
+}
+
+#[test]
+fn deserialize_str() {
+    assert_eq!(
+        deserialize::<Vec2<i32>, _>(deserializer("(x=3, y=-5)")),
+        Ok(Vec2 { x: 3, y: -5 })
+    );
+    assert_eq!(
+        deserialize::<Rect<u8>, _>(deserializer("(x=1, y=2)..(x=4, y=3)")),
+        Ok(Rect { start: Vec2 { x: 1, y: 2 }, size: Vec2 { x: 3, y: 1 } })
+    );
+    assert_eq!(
+        deserialize::<Direction, _>(deserializer("left")),
+        Ok(Direction::Left)
+    );
+    assert_eq!(deserialize::<Axis, _>(deserializer("x")), Ok(Axis::X));
+    assert!(deserialize::<Vec2<i32>, _>(deserializer("(3; -5)")).is_err());
+}
+
+#[test]
+fn round_trip_rect_at_bound() {
+    let rect: Rect<u8> =
+        Rect { start: Vec2 { x: 200, y: 0 }, size: Vec2 { x: 56, y: 3 } };
+    let serialized = Serialized(&rect).to_string();
+    assert_eq!(serialized, "(x=200, y=0)+(x=56, y=3)");
+    assert_eq!(deserialize::<Rect<u8>, _>(deserializer(&serialized)), Ok(rect));
+
+    let rect: Rect<u8> =
+        Rect { start: Vec2 { x: 200, y: 0 }, size: Vec2 { x: 55, y: 3 } };
+    let serialized = Serialized(&rect).to_string();
+    assert_eq!(serialized, "(x=200, y=0)..(x=255, y=3)");
+    assert_eq!(deserialize::<Rect<u8>, _>(deserializer(&serialized)), Ok(rect));
+}
@@ -136,7 +136,17 @@ pub mod bits;
 pub mod axis;
 pub mod direc;
 pub mod coord;
+pub mod line;
+pub mod segment;
+pub mod shape;
+pub mod fov;
 pub mod rect;
 pub mod map;
 pub mod set;
+pub mod path;
 pub mod graph;
+pub mod hierarchy;
+pub mod replan;
+pub mod clearance;
+#[cfg(feature = "impl-serde")]
+pub mod human_readable;
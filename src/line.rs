@@ -0,0 +1,309 @@
+//! This module provides rasterization of arbitrary (i.e. not necessarily
+//! axis-aligned) lines between two points, using Bresenham's algorithm.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    axis::Axis,
+    bits::UnsignedDistance,
+    coord::Vec2,
+    direc::Direction,
+};
+use num::{One, Unsigned};
+use std::{
+    cmp::Ordering,
+    ops::{Add, Sub},
+};
+
+/// How consecutive points of a [`Line`] are connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Connectivity {
+    /// Consecutive points differ in exactly one axis, by one unit. Whenever
+    /// the line changes both coordinates, the cell it crosses first is also
+    /// yielded (AKA "supercover"). A tie (the line crossing exactly through a
+    /// cell corner) is resolved by moving along the major axis first.
+    Four,
+    /// Consecutive points may differ diagonally. This is the classic
+    /// Bresenham line, which yields one point per unit along the major axis.
+    Eight,
+}
+
+/// Iterator over all grid points of a line from a starting point to an ending
+/// point, both inclusive. The major axis is the one with the greatest
+/// distance between the points (`X` on a tie), and points are rounded to the
+/// nearest coordinate on the minor axis, with halves rounded towards the
+/// ending point.
+///
+/// The iterator is double-ended, and iterating from the back yields exactly
+/// the same points as iterating from the front, in reverse order. No
+/// coordinate arithmetic can overflow, even when the points are at the bounds
+/// of the coordinate type.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     coord::Vec2,
+///     line::{Connectivity, Line},
+/// };
+///
+/// # fn main() {
+/// let start = Vec2 { x: 0u8, y: 0 };
+/// let end = Vec2 { x: 4, y: 2 };
+///
+/// let points: Vec<_> = Line::new(start, end, Connectivity::Eight).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 0, y: 0 },
+///         Vec2 { x: 1, y: 1 },
+///         Vec2 { x: 2, y: 1 },
+///         Vec2 { x: 3, y: 2 },
+///         Vec2 { x: 4, y: 2 },
+///     ]
+/// );
+///
+/// let points: Vec<_> = Line::new(start, end, Connectivity::Four).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 0, y: 0 },
+///         Vec2 { x: 1, y: 0 },
+///         Vec2 { x: 1, y: 1 },
+///         Vec2 { x: 2, y: 1 },
+///         Vec2 { x: 3, y: 1 },
+///         Vec2 { x: 3, y: 2 },
+///         Vec2 { x: 4, y: 2 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Line<T, D = <T as UnsignedDistance>::Output>
+where
+    T: UnsignedDistance<Output = D>,
+{
+    inner: Option<LineInner<T, D>>,
+}
+
+impl<T, D> Line<T, D>
+where
+    T: UnsignedDistance<Output = D> + Ord + Clone,
+    D: Unsigned + Ord + Clone,
+{
+    /// Creates a line from the given starting point to the given ending point,
+    /// both inclusive, with the given connectivity.
+    pub fn new(
+        start: Vec2<T>,
+        end: Vec2<T>,
+        connectivity: Connectivity,
+    ) -> Self {
+        let dists = start
+            .as_ref()
+            .zip_with(end.as_ref(), |start, end| start.unsigned_distance(end));
+        let major_axis = if dists.x >= dists.y { Axis::X } else { Axis::Y };
+        let minor_axis = !major_axis;
+        let direction_on = |axis| {
+            if end[axis] >= start[axis] {
+                Direction::from_axis_pos(axis)
+            } else {
+                Direction::from_axis_neg(axis)
+            }
+        };
+        let major = direction_on(major_axis);
+        let minor = direction_on(minor_axis);
+        let Vec2 { x: dist_x, y: dist_y } = dists;
+        let (major_dist, minor_dist) = match major_axis {
+            Axis::X => (dist_x, dist_y),
+            Axis::Y => (dist_y, dist_x),
+        };
+        let two = D::one() + D::one();
+        let half = major_dist.clone() / two;
+        let odd = half.clone() + half.clone() != major_dist;
+
+        let inner = LineInner {
+            connectivity,
+            major,
+            minor,
+            front: Cursor {
+                point: start,
+                error: half.clone(),
+                index: D::zero(),
+                middle: None,
+            },
+            back: Cursor {
+                point: end,
+                error: half,
+                index: major_dist.clone(),
+                middle: None,
+            },
+            major_dist,
+            minor_dist,
+            odd,
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl<T, D> Iterator for Line<T, D>
+where
+    T: UnsignedDistance<Output = D>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + One
+        + Clone,
+    D: Unsigned + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = self.inner.as_mut()?;
+        let (item, is_middle) = match inner.front.middle.take() {
+            Some(middle) => (middle, true),
+            None => (inner.front.point.clone(), false),
+        };
+        if inner.cursors_met(is_middle, inner.back.middle.is_some()) {
+            self.inner = None;
+        } else if !is_middle {
+            inner.step_front();
+        }
+        Some(item)
+    }
+}
+
+impl<T, D> DoubleEndedIterator for Line<T, D>
+where
+    T: UnsignedDistance<Output = D>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + One
+        + Clone,
+    D: Unsigned + Ord + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let inner = self.inner.as_mut()?;
+        let (item, is_middle) = match inner.back.middle.take() {
+            Some(middle) => (middle, true),
+            None => (inner.back.point.clone(), false),
+        };
+        if inner.cursors_met(inner.front.middle.is_some(), is_middle) {
+            self.inner = None;
+        } else if is_middle {
+            inner.back.index = inner.back.index.clone() - D::one();
+        } else {
+            inner.step_back();
+        }
+        Some(item)
+    }
+}
+
+/// One of the ends of a line being iterated.
+///
+/// `index` is the index of the "main" point along the major axis. If `middle`
+/// is present, it is the next point to be yielded, and it lies just before
+/// the main point of index `index`. For the front cursor, `point` is the main
+/// point of index `index`, while, for the back cursor, `point` is the main
+/// point right before the middle point (if any).
+#[derive(Debug, Clone)]
+struct Cursor<T, D> {
+    point: Vec2<T>,
+    error: D,
+    index: D,
+    middle: Option<Vec2<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct LineInner<T, D> {
+    connectivity: Connectivity,
+    major: Direction,
+    minor: Direction,
+    major_dist: D,
+    minor_dist: D,
+    odd: bool,
+    front: Cursor<T, D>,
+    back: Cursor<T, D>,
+}
+
+impl<T, D> LineInner<T, D>
+where
+    T: Add<Output = T> + Sub<Output = T> + One + Clone,
+    D: Unsigned + Ord + Clone,
+{
+    /// Tests whether the item just taken from a cursor was the last one, i.e.
+    /// whether the front cursor reached the back cursor. The arguments tell
+    /// whether the next item of each cursor is (or was, if just taken) a
+    /// middle point.
+    fn cursors_met(&self, front_middle: bool, back_middle: bool) -> bool {
+        match self.front.index.cmp(&self.back.index) {
+            Ordering::Less => false,
+            Ordering::Equal => !front_middle || back_middle,
+            Ordering::Greater => true,
+        }
+    }
+
+    /// Tests whether the line crosses the minor-axis cell boundary before the
+    /// major-axis cell boundary, given the error term of the main point right
+    /// after a diagonal step.
+    fn minor_first(&self, error: &D) -> bool {
+        // With an even major distance this is `2 * error > minor_dist`,
+        // otherwise `2 * error >= minor_dist`, written so it cannot overflow.
+        if *error > self.minor_dist {
+            return true;
+        }
+        let remaining = self.minor_dist.clone() - error.clone();
+        if self.odd {
+            *error >= remaining
+        } else {
+            *error > remaining
+        }
+    }
+
+    fn middle_point(&self, previous: &Vec2<T>, error: &D) -> Vec2<T> {
+        let direction =
+            if self.minor_first(error) { self.minor } else { self.major };
+        previous.clone().move_one(direction)
+    }
+
+    fn step_front(&mut self) {
+        let previous = self.front.point.clone();
+        let threshold = self.major_dist.clone() - self.minor_dist.clone();
+        let mut point = previous.clone().move_one(self.major);
+        let diagonal = self.front.error >= threshold;
+        if diagonal {
+            self.front.error = self.front.error.clone() - threshold;
+            point = point.move_one(self.minor);
+        } else {
+            self.front.error =
+                self.front.error.clone() + self.minor_dist.clone();
+        }
+        self.front.index = self.front.index.clone() + D::one();
+        if diagonal && self.connectivity == Connectivity::Four {
+            self.front.middle =
+                Some(self.middle_point(&previous, &self.front.error));
+        }
+        self.front.point = point;
+    }
+
+    fn step_back(&mut self) {
+        let error = self.back.error.clone();
+        let mut point = self.back.point.clone().move_one(!self.major);
+        let diagonal = self.back.error < self.minor_dist;
+        if diagonal {
+            let threshold = self.major_dist.clone() - self.minor_dist.clone();
+            self.back.error = self.back.error.clone() + threshold;
+            point = point.move_one(!self.minor);
+        } else {
+            self.back.error = self.back.error.clone() - self.minor_dist.clone();
+        }
+        if diagonal && self.connectivity == Connectivity::Four {
+            self.back.middle = Some(self.middle_point(&point, &error));
+        } else {
+            self.back.index = self.back.index.clone() - D::one();
+        }
+        self.back.point = point;
+    }
+}
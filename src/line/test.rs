@@ -0,0 +1,199 @@
+use super::{Connectivity, Line};
+use crate::coord::Vec2;
+
+/// Straightforward (but overflowing for large coordinates) implementation of
+/// the line rasterization, used as a reference.
+fn reference(
+    start: Vec2<i64>,
+    end: Vec2<i64>,
+    connectivity: Connectivity,
+) -> Vec<Vec2<i64>> {
+    let dist = Vec2 { x: (end.x - start.x).abs(), y: (end.y - start.y).abs() };
+    let sign =
+        Vec2 { x: (end.x - start.x).signum(), y: (end.y - start.y).signum() };
+    let x_major = dist.x >= dist.y;
+    let (major_dist, minor_dist) =
+        if x_major { (dist.x, dist.y) } else { (dist.y, dist.x) };
+    let make_point = |major: i64, minor: i64| {
+        if x_major {
+            Vec2 { x: start.x + sign.x * major, y: start.y + sign.y * minor }
+        } else {
+            Vec2 { x: start.x + sign.x * minor, y: start.y + sign.y * major }
+        }
+    };
+    let minor_at = |index: i64| {
+        if major_dist == 0 {
+            0
+        } else {
+            (index * minor_dist + major_dist / 2) / major_dist
+        }
+    };
+
+    let mut points = vec![make_point(0, 0)];
+    for index in 1 ..= major_dist {
+        let minor = minor_at(index);
+        let prev_minor = minor_at(index - 1);
+        if connectivity == Connectivity::Four && minor != prev_minor {
+            // Minor boundary crossed at major = (2 * prev_minor + 1) * D /
+            // (2 * d), major boundary at index - 1/2.
+            let minor_first = (2 * prev_minor + 1) * major_dist
+                < (2 * index - 1) * minor_dist;
+            if minor_first {
+                points.push(make_point(index - 1, minor));
+            } else {
+                points.push(make_point(index, prev_minor));
+            }
+        }
+        points.push(make_point(index, minor));
+    }
+    points
+}
+
+fn cast_points(points: Vec<Vec2<i64>>) -> Vec<Vec2<i32>> {
+    points.into_iter().map(|point| point.map(|coord| coord as i32)).collect()
+}
+
+#[test]
+fn eight_connected_matches_reference() {
+    for start_x in -4 ..= 4 {
+        for start_y in -4 ..= 4 {
+            for end_x in -4 ..= 4 {
+                for end_y in -4 ..= 4 {
+                    let start = Vec2 { x: start_x, y: start_y };
+                    let end = Vec2 { x: end_x, y: end_y };
+                    let points: Vec<_> =
+                        Line::new(start, end, Connectivity::Eight).collect();
+                    let expected = cast_points(reference(
+                        start.map(i64::from),
+                        end.map(i64::from),
+                        Connectivity::Eight,
+                    ));
+                    assert_eq!(points, expected, "{} to {}", start, end);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn four_connected_matches_reference() {
+    for start_x in -4 ..= 4 {
+        for start_y in -4 ..= 4 {
+            for end_x in -4 ..= 4 {
+                for end_y in -4 ..= 4 {
+                    let start = Vec2 { x: start_x, y: start_y };
+                    let end = Vec2 { x: end_x, y: end_y };
+                    let points: Vec<_> =
+                        Line::new(start, end, Connectivity::Four).collect();
+                    let expected = cast_points(reference(
+                        start.map(i64::from),
+                        end.map(i64::from),
+                        Connectivity::Four,
+                    ));
+                    assert_eq!(points, expected, "{} to {}", start, end);
+                    for pair in points.windows(2) {
+                        let dist = pair[0].manhattan_dist(pair[1]);
+                        assert_eq!(dist, 1, "{} to {}", start, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn double_ended() {
+    let start = Vec2 { x: 3, y: -2 };
+    for end in [
+        Vec2 { x: 3, y: -2 },
+        Vec2 { x: 10, y: 1 },
+        Vec2 { x: -4, y: 7 },
+        Vec2 { x: 0, y: -13 },
+        Vec2 { x: 9, y: -8 },
+    ] {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let forward: Vec<_> = Line::new(start, end, connectivity).collect();
+            let mut backward: Vec<_> =
+                Line::new(start, end, connectivity).rev().collect();
+            backward.reverse();
+            assert_eq!(forward, backward);
+
+            for split in 0 ..= forward.len() {
+                let mut line = Line::new(start, end, connectivity);
+                let front: Vec<_> = line.by_ref().take(split).collect();
+                let mut back: Vec<_> = line.by_ref().rev().collect();
+                back.reverse();
+                assert_eq!(front, forward[.. split]);
+                assert_eq!(back, forward[split ..]);
+                assert_eq!(line.next(), None);
+                assert_eq!(line.next_back(), None);
+            }
+
+            let mut line = Line::new(start, end, connectivity);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            while let Some(point) = line.next() {
+                front.push(point);
+                match line.next_back() {
+                    Some(point) => back.push(point),
+                    None => break,
+                }
+            }
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, forward);
+        }
+    }
+}
+
+#[test]
+fn signed_bounds() {
+    let start = Vec2 { x: i8::MIN, y: i8::MAX };
+    let end = Vec2 { x: i8::MAX, y: i8::MIN + 100 };
+    let points: Vec<_> = Line::new(start, end, Connectivity::Eight).collect();
+    assert_eq!(points.len(), 256);
+    assert_eq!(points.first(), Some(&start));
+    assert_eq!(points.last(), Some(&end));
+    let expected: Vec<_> = reference(
+        start.map(i64::from),
+        end.map(i64::from),
+        Connectivity::Eight,
+    )
+    .into_iter()
+    .map(|point| point.map(|coord| coord as i8))
+    .collect();
+    assert_eq!(points, expected);
+
+    let start = Vec2 { x: i8::MIN, y: i8::MIN };
+    let end = Vec2 { x: i8::MAX, y: i8::MAX };
+    let points: Vec<_> =
+        Line::new(end, start, Connectivity::Four).rev().collect();
+    assert_eq!(points.len(), 511);
+    assert_eq!(points.first(), Some(&start));
+    assert_eq!(points.last(), Some(&end));
+}
+
+#[test]
+fn unsigned_bounds() {
+    let start = Vec2 { x: u8::MAX, y: 0 };
+    let end = Vec2 { x: 0, y: u8::MAX };
+    let points: Vec<_> = Line::new(start, end, Connectivity::Four).collect();
+    assert_eq!(points.len(), 511);
+    assert_eq!(points.first(), Some(&start));
+    assert_eq!(points.last(), Some(&end));
+
+    let start = Vec2 { x: u64::MAX, y: u64::MAX - 2 };
+    let end = Vec2 { x: u64::MAX - 5, y: u64::MAX };
+    let points: Vec<_> = Line::new(start, end, Connectivity::Eight).collect();
+    assert_eq!(
+        points,
+        vec![
+            Vec2 { x: u64::MAX, y: u64::MAX - 2 },
+            Vec2 { x: u64::MAX - 1, y: u64::MAX - 2 },
+            Vec2 { x: u64::MAX - 2, y: u64::MAX - 1 },
+            Vec2 { x: u64::MAX - 3, y: u64::MAX - 1 },
+            Vec2 { x: u64::MAX - 4, y: u64::MAX },
+            Vec2 { x: u64::MAX - 5, y: u64::MAX },
+        ]
+    );
+}
@@ -4,12 +4,18 @@
 #[cfg(test)]
 mod test;
 
-use crate::{coord::Vec2, direc::Direction};
+use crate::{
+    bits::{ExcessToSigned, HalfExcess},
+    coord::Vec2,
+    direc::Direction,
+};
+use num::One;
 use std::{
     borrow::Borrow,
     collections::{btree_map, BTreeMap},
     iter::FromIterator,
     mem,
+    ops::{Bound, Neg, Sub},
 };
 
 #[cfg(feature = "impl-serde")]
@@ -107,7 +113,65 @@ where
         K: Borrow<Q>,
         Q: Ord,
     {
-        Neighbours { inner: NeighboursInner::new(self, point, direction) }
+        if self.contains(point) {
+            self.neighbours_from_incl(point, direction)
+        } else {
+            Neighbours { inner: None }
+        }
+    }
+
+    /// Returns an iterator to the points in a straight line from a given
+    /// point in the given direction. Unlike [`Map::neighbours`], the starting
+    /// point does not need to be in the map. The starting point is NOT
+    /// included. For every point yielded by the iterator, the associated data
+    /// is also returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, direc::Direction, map::Map};
+    ///
+    /// # fn main() {
+    /// let mut map = Map::new();
+    /// map.insert(Vec2 { x: 2u8, y: 5 }, 'a');
+    /// map.insert(Vec2 { x: 7, y: 5 }, 'b');
+    /// let start = Vec2 { x: 4, y: 5 };
+    ///
+    /// assert_eq!(map.neighbours(start.as_ref(), Direction::Right).next(), None);
+    /// assert_eq!(
+    ///     map.neighbours_from(start.as_ref(), Direction::Right).next(),
+    ///     Some((Vec2 { x: &7, y: &5 }, &'b'))
+    /// );
+    /// # }
+    /// ```
+    pub fn neighbours_from<Q>(
+        &self,
+        point: Vec2<&Q>,
+        direction: Direction,
+    ) -> Neighbours<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Neighbours {
+            inner: NeighboursInner::new(self, point, direction, false),
+        }
+    }
+
+    /// Returns an iterator to the points in a straight line from a given
+    /// point in the given direction. Unlike [`Map::neighbours_incl`], the
+    /// starting point does not need to be in the map. The starting point IS
+    /// included, if it is in the map. For every point yielded by the
+    /// iterator, the associated data is also returned.
+    pub fn neighbours_from_incl<Q>(
+        &self,
+        point: Vec2<&Q>,
+        direction: Direction,
+    ) -> Neighbours<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        Neighbours { inner: NeighboursInner::new(self, point, direction, true) }
     }
 
     /// Returns the nearest neighbour in a straight line of a given point in the
@@ -316,6 +380,126 @@ where
             },
         }
     }
+
+    /// Builds a new map by converting every key with the given function,
+    /// keeping the associated data. If two keys are converted into the same
+    /// key, only the entry visited last in the direction of rows is kept.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, map::Map};
+    ///
+    /// # fn main() {
+    /// let mut map = Map::<u16, &str>::new();
+    /// map.insert(Vec2 { x: 5, y: 3 }, "foo");
+    /// let mapped = map.map_keys(|key| key.copied() * Vec2 { x: 2, y: 2 });
+    /// assert_eq!(mapped.get(Vec2 { x: 10, y: 6 }.as_ref()), Some(&"foo"));
+    /// # }
+    /// ```
+    pub fn map_keys<F, U>(&self, mut mapper: F) -> Map<U, V>
+    where
+        F: FnMut(Vec2<&K>) -> Vec2<U>,
+        U: Ord + Clone,
+        V: Clone,
+    {
+        self.rows().map(|(key, value)| (mapper(key), value.clone())).collect()
+    }
+
+    /// Builds a new map by converting every key with the given fallible
+    /// function, keeping the associated data. The first error found (in the
+    /// direction of rows) is returned. If two keys are converted into the same
+    /// key, only the entry visited last in the direction of rows is kept.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, map::Map};
+    ///
+    /// # fn main() {
+    /// let mut map = Map::<u16, &str>::new();
+    /// map.insert(Vec2 { x: 5, y: 3 }, "foo");
+    /// map.insert(Vec2 { x: 1, y: 9 }, "bar");
+    ///
+    /// let converted = map.try_map_keys(|key| key.copied().try_cast::<i8>());
+    /// let converted = converted.unwrap();
+    /// assert_eq!(converted.get(Vec2 { x: 5, y: 3 }.as_ref()), Some(&"foo"));
+    /// assert_eq!(converted.get(Vec2 { x: 1, y: 9 }.as_ref()), Some(&"bar"));
+    ///
+    /// map.insert(Vec2 { x: 500, y: 3 }, "baz");
+    /// assert!(map.try_map_keys(|key| key.copied().try_cast::<i8>()).is_err());
+    /// # }
+    /// ```
+    pub fn try_map_keys<F, U, E>(&self, mut mapper: F) -> Result<Map<U, V>, E>
+    where
+        F: FnMut(Vec2<&K>) -> Result<Vec2<U>, E>,
+        U: Ord + Clone,
+        V: Clone,
+    {
+        self.rows()
+            .map(|(key, value)| Ok((mapper(key)?, value.clone())))
+            .collect()
+    }
+
+    /// Converts every key from an "excess of N" number into a 2's complement
+    /// number, keeping the associated data. See [`ExcessToSigned`].
+    pub fn excess_to_signed<U>(&self, excess: &Vec2<K>) -> Map<U, V>
+    where
+        K: Clone,
+        Vec2<K>: ExcessToSigned<Target = Vec2<U>>,
+        U: Ord + Clone,
+        V: Clone,
+    {
+        self.map_keys(|key| key.cloned().excess_to_signed(excess))
+    }
+
+    /// Converts every key from an "excess of N" number into a 2's complement
+    /// number, where `N` is half the maximum value of the unsigned key type,
+    /// keeping the associated data. See [`ExcessToSigned`].
+    pub fn half_exc_to_signed<U>(&self) -> Map<U, V>
+    where
+        K: Clone,
+        Vec2<K>: ExcessToSigned<Target = Vec2<U>> + HalfExcess,
+        U: Ord + Clone,
+        V: Clone,
+    {
+        self.map_keys(|key| key.cloned().half_exc_to_signed())
+    }
+
+    /// Re-centers every key at the given origin and flips its Y coordinate,
+    /// keeping the associated data. See [`Vec2::center_origin_at`].
+    pub fn center_origin_at<U>(&self, origin: &Vec2<K>) -> Map<U, V>
+    where
+        K: Clone,
+        Vec2<K>: ExcessToSigned<Target = Vec2<U>>,
+        U: Sub<Output = U> + Neg<Output = U> + One + Ord + Clone,
+        V: Clone,
+    {
+        self.map_keys(|key| key.cloned().center_origin_at(origin))
+    }
+
+    /// Re-centers every key at half the maximum value of the unsigned key type
+    /// and flips its Y coordinate, keeping the associated data. See
+    /// [`Vec2::center_origin`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, map::Map};
+    ///
+    /// # fn main() {
+    /// let mut map = Map::<u8, &str>::new();
+    /// map.insert(Vec2 { x: 127, y: 130 }, "foo");
+    /// let centered: Map<i8, &str> = map.center_origin();
+    /// assert_eq!(centered.get(Vec2 { x: -1, y: -3 }.as_ref()), Some(&"foo"));
+    /// # }
+    /// ```
+    pub fn center_origin<U>(&self) -> Map<U, V>
+    where
+        K: Clone,
+        Vec2<K>: ExcessToSigned<Target = Vec2<U>> + HalfExcess,
+        U: Sub<Output = U> + Neg<Output = U> + One + Ord + Clone,
+        V: Clone,
+    {
+        self.map_keys(|key| key.cloned().center_origin())
+    }
 }
 
 impl<K, V> PartialEq for Map<K, V>
@@ -443,8 +627,9 @@ where
     }
 }
 
-/// Iterator over the neighbours of a point in a map. See [`Map::neighbours`]
-/// and [`Map::neighbours_incl`].
+/// Iterator over the neighbours of a point in a map. See [`Map::neighbours`],
+/// [`Map::neighbours_incl`], [`Map::neighbours_from`] and
+/// [`Map::neighbours_from_incl`].
 #[derive(Debug, Clone)]
 pub struct Neighbours<'map, K, V>
 where
@@ -527,50 +712,43 @@ where
         map: &'map Map<K, V>,
         point: Vec2<&'param Q>,
         direction: Direction,
+        inclusive: bool,
     ) -> Option<Self>
     where
         K: Borrow<Q>,
         Q: Ord,
     {
+        let bound = |key| {
+            if inclusive {
+                Bound::Included(key)
+            } else {
+                Bound::Excluded(key)
+            }
+        };
+
         match direction {
             Direction::Up => {
                 let (key, table) = map.neighbours.x.get_key_value(point.x)?;
-                if table.contains_key(point.y) {
-                    let range = table.range(..= point.y);
-                    Some(Self { key, direction, range })
-                } else {
-                    None
-                }
+                let range = table.range((Bound::Unbounded, bound(point.y)));
+                Some(Self { key, direction, range })
             },
 
             Direction::Down => {
-                let (key, table) = map.neighbours.x.get_key_value(&point.x)?;
-                if table.contains_key(point.y) {
-                    let range = table.range(point.y ..);
-                    Some(Self { key, direction, range })
-                } else {
-                    None
-                }
+                let (key, table) = map.neighbours.x.get_key_value(point.x)?;
+                let range = table.range((bound(point.y), Bound::Unbounded));
+                Some(Self { key, direction, range })
             },
 
             Direction::Left => {
-                let (key, table) = map.neighbours.y.get_key_value(&point.y)?;
-                if table.contains_key(point.x) {
-                    let range = table.range(..= point.x);
-                    Some(Self { key, direction, range })
-                } else {
-                    None
-                }
+                let (key, table) = map.neighbours.y.get_key_value(point.y)?;
+                let range = table.range((Bound::Unbounded, bound(point.x)));
+                Some(Self { key, direction, range })
             },
 
             Direction::Right => {
-                let (key, table) = map.neighbours.y.get_key_value(&point.y)?;
-                if table.contains_key(point.x) {
-                    let range = table.range(point.x ..);
-                    Some(Self { key, direction, range })
-                } else {
-                    None
-                }
+                let (key, table) = map.neighbours.y.get_key_value(point.y)?;
+                let range = table.range((bound(point.x), Bound::Unbounded));
+                Some(Self { key, direction, range })
             },
         }
     }
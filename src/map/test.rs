@@ -282,6 +282,62 @@ fn neighbours() {
     assert_eq!(collect(Vec2 { x: 2099, y: 4 }.as_ref(), Direction::Right), &[],);
 }
 
+#[test]
+fn neighbours_from() {
+    let map = make_map();
+    let collect = |key, direc| {
+        map.neighbours_from(key, direc)
+            .map(|(key, val)| (key.copied(), *val))
+            .collect::<Vec<_>>()
+    };
+    let collect_incl = |key, direc| {
+        map.neighbours_from_incl(key, direc)
+            .map(|(key, val)| (key.copied(), *val))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        collect(Vec2 { x: 0, y: 3 }.as_ref(), Direction::Up),
+        &[(Vec2 { x: 0, y: 2 }, "have"), (Vec2 { x: 0, y: -2 }, "very")],
+    );
+    assert_eq!(
+        collect_incl(Vec2 { x: 0, y: 3 }.as_ref(), Direction::Down),
+        &[(Vec2 { x: 0, y: 5 }, "a"), (Vec2 { x: 0, y: 569 }, "(really)")],
+    );
+    assert_eq!(
+        collect(Vec2 { x: 5, y: -2 }.as_ref(), Direction::Left),
+        &[
+            (Vec2 { x: 1, y: -2 }, "and"),
+            (Vec2 { x: 0, y: -2 }, "very"),
+            (Vec2 { x: -1, y: -2 }, "(eh)"),
+        ],
+    );
+    assert_eq!(
+        collect_incl(Vec2 { x: 5, y: -2 }.as_ref(), Direction::Right),
+        &[(Vec2 { x: 9, y: -2 }, "nice"), (Vec2 { x: 100, y: -2 }, "day")],
+    );
+    assert_eq!(collect(Vec2 { x: 5, y: 7 }.as_ref(), Direction::Up), &[],);
+    assert_eq!(
+        collect_incl(Vec2 { x: 0, y: 5 }.as_ref(), Direction::Down),
+        &[(Vec2 { x: 0, y: 5 }, "a"), (Vec2 { x: 0, y: 569 }, "(really)")],
+    );
+
+    // Neighbours of points not in the map are only found by the above.
+    let absent = Vec2 { x: 3, y: 5 };
+    assert_eq!(map.neighbours(absent.as_ref(), Direction::Left).next(), None);
+    assert_eq!(
+        map.neighbours_incl(absent.as_ref(), Direction::Left).next(),
+        None
+    );
+    assert_eq!(map.first_neighbour(absent.as_ref(), Direction::Left), None);
+    assert_eq!(
+        map.neighbours_from(absent.as_ref(), Direction::Left)
+            .next()
+            .map(|(key, _)| key),
+        Some(Vec2 { x: 0, y: 5 }.as_ref())
+    );
+}
+
 #[test]
 fn first_neighbour() {
     let map = make_map();
@@ -445,3 +501,51 @@ fn equals() {
     copied.insert(Vec2 { x: -2, y: -2 }, "koyotl");
     assert_ne!(map, copied);
 }
+
+#[test]
+fn try_map_keys() {
+    let mut map = Map::<u16, &str>::new();
+    map.insert(Vec2 { x: 9, y: 12 }, "avocado");
+    map.insert(Vec2 { x: 8, y: 17 }, "banana");
+
+    let converted =
+        map.try_map_keys(|key| key.copied().try_cast::<i8>()).unwrap();
+    assert_eq!(converted.len(), 2);
+    assert_eq!(converted.get(Vec2 { x: 9, y: 12 }.as_ref()), Some(&"avocado"));
+    assert_eq!(converted.get(Vec2 { x: 8, y: 17 }.as_ref()), Some(&"banana"));
+
+    map.insert(Vec2 { x: 8, y: 1700 }, "nihil");
+    assert!(map.try_map_keys(|key| key.copied().try_cast::<i8>()).is_err());
+
+    let mapped = map.map_keys(|key| Vec2 { x: *key.x / 2, y: 0 });
+    assert_eq!(mapped.len(), 1);
+    assert_eq!(mapped.get(Vec2 { x: 4, y: 0 }.as_ref()), Some(&"nihil"));
+}
+
+#[test]
+fn center_origin() {
+    let mut map = Map::<u8, &str>::new();
+    map.insert(Vec2 { x: 3, y: 130 }, "avocado");
+    map.insert(Vec2 { x: 128, y: 127 }, "banana");
+
+    let centered = map.center_origin();
+    assert_eq!(centered.len(), 2);
+    assert_eq!(
+        centered.get(Vec2 { x: -125, y: -3 }.as_ref()),
+        Some(&"avocado")
+    );
+    assert_eq!(centered.get(Vec2 { x: 0, y: 0 }.as_ref()), Some(&"banana"));
+
+    let centered = map.center_origin_at(&Vec2 { x: 20, y: 30 });
+    assert_eq!(
+        centered.get(Vec2 { x: -17, y: -101 }.as_ref()),
+        Some(&"avocado")
+    );
+
+    let signed = map.half_exc_to_signed();
+    assert_eq!(signed.get(Vec2 { x: -125, y: 2 }.as_ref()), Some(&"avocado"));
+    assert_eq!(signed.get(Vec2 { x: 0, y: -1 }.as_ref()), Some(&"banana"));
+
+    let signed = map.excess_to_signed(&Vec2 { x: 3, y: 3 });
+    assert_eq!(signed.get(Vec2 { x: 0, y: 127 }.as_ref()), Some(&"avocado"));
+}
@@ -0,0 +1,227 @@
+//! This module provides paths made of straight steps, as found by the path
+//! making algorithms of [`Graph`](crate::graph::Graph).
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+};
+use num::{One, Zero};
+use std::{
+    ops::{Add, Sub},
+    slice,
+};
+
+/// A path in a plane, starting at a point and following a list of straight
+/// steps, together with the cost of the path. In paths found by the graph,
+/// every step but the last one ends at a turn, and the cost includes the
+/// penalties for turning. The end of the path is assumed to be representable
+/// by `T`.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     coord::Vec2,
+///     direc::{DirecVector, Direction},
+///     path::Path,
+/// };
+///
+/// # fn main() {
+/// let path = Path::new(
+///     Vec2 { x: 1u8, y: 1 },
+///     vec![
+///         DirecVector { direction: Direction::Right, magnitude: 2 },
+///         DirecVector { direction: Direction::Down, magnitude: 1 },
+///     ],
+///     5,
+/// );
+/// assert_eq!(path.end(), Vec2 { x: 3, y: 2 });
+/// assert_eq!(path.length(), 3);
+/// assert_eq!(path.turns(), 1);
+/// assert_eq!(
+///     path.points().collect::<Vec<_>>(),
+///     vec![
+///         Vec2 { x: 1, y: 1 },
+///         Vec2 { x: 2, y: 1 },
+///         Vec2 { x: 3, y: 1 },
+///         Vec2 { x: 3, y: 2 },
+///     ]
+/// );
+/// assert_eq!(
+///     path.vertices().collect::<Vec<_>>(),
+///     vec![Vec2 { x: 1, y: 1 }, Vec2 { x: 3, y: 1 }, Vec2 { x: 3, y: 2 }]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Path<T> {
+    /// Starting point.
+    pub start: Vec2<T>,
+    /// Steps from the start till the end.
+    pub steps: Vec<DirecVector<T>>,
+    /// Total cost of the path.
+    pub cost: T,
+}
+
+impl<T> Path<T> {
+    /// Creates a path from its start, its steps and its cost.
+    pub fn new(start: Vec2<T>, steps: Vec<DirecVector<T>>, cost: T) -> Self {
+        Self { start, steps, cost }
+    }
+
+    /// Returns the point where the path ends.
+    pub fn end(&self) -> Vec2<T>
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        self.steps
+            .iter()
+            .fold(self.start.clone(), |point, step| point.move_by(step.clone()))
+    }
+
+    /// Total length of the path, i.e. the sum of the magnitudes of the steps,
+    /// without penalties.
+    pub fn length(&self) -> T
+    where
+        T: Clone + Add<Output = T> + Zero,
+    {
+        self.steps
+            .iter()
+            .fold(T::zero(), |length, step| length + step.magnitude.clone())
+    }
+
+    /// How many times the path turns, i.e. how many consecutive steps have
+    /// different directions.
+    pub fn turns(&self) -> usize {
+        self.steps
+            .windows(2)
+            .filter(|pair| pair[0].direction != pair[1].direction)
+            .count()
+    }
+
+    /// Iterator over every point visited by the path, from the start till the
+    /// end, both included.
+    pub fn points(&self) -> Points<'_, T>
+    where
+        T: Clone + Zero,
+    {
+        Points {
+            current: Some(self.start.clone()),
+            steps: self.steps.iter(),
+            direction: Direction::Right,
+            remaining: T::zero(),
+        }
+    }
+
+    /// Iterator over the vertices of the path, i.e. the start and the end of
+    /// every step. In paths found by the graph, these are the start, the
+    /// corners and the end.
+    pub fn vertices(&self) -> Vertices<'_, T>
+    where
+        T: Clone,
+    {
+        Vertices { current: Some(self.start.clone()), steps: self.steps.iter() }
+    }
+
+    /// Reverses this path in place, so that it starts where it used to end
+    /// and follows the same points backwards. The cost is kept as it is.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     path::Path,
+    /// };
+    ///
+    /// # fn main() {
+    /// let mut path = Path::new(
+    ///     Vec2 { x: 0i32, y: 0 },
+    ///     vec![
+    ///         DirecVector { direction: Direction::Up, magnitude: 4 },
+    ///         DirecVector { direction: Direction::Left, magnitude: 1 },
+    ///     ],
+    ///     7,
+    /// );
+    /// path.reverse();
+    /// assert_eq!(path.start, Vec2 { x: -1, y: -4 });
+    /// assert_eq!(
+    ///     path.steps,
+    ///     vec![
+    ///         DirecVector { direction: Direction::Right, magnitude: 1 },
+    ///         DirecVector { direction: Direction::Down, magnitude: 4 },
+    ///     ]
+    /// );
+    /// assert_eq!(path.end(), Vec2 { x: 0, y: 0 });
+    /// # }
+    /// ```
+    pub fn reverse(&mut self)
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        self.start = self.end();
+        self.steps.reverse();
+        for step in &mut self.steps {
+            step.direction = !step.direction;
+        }
+    }
+}
+
+/// Iterator over the points visited by a path. See [`Path::points`].
+#[derive(Debug, Clone)]
+pub struct Points<'path, T> {
+    current: Option<Vec2<T>>,
+    steps: slice::Iter<'path, DirecVector<T>>,
+    direction: Direction,
+    remaining: T,
+}
+
+impl<'path, T> Iterator for Points<'path, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Zero + One,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.take()?;
+        while self.remaining.is_zero() {
+            let step = match self.steps.next() {
+                Some(step) => step,
+                None => return Some(current),
+            };
+            self.direction = step.direction;
+            self.remaining = step.magnitude.clone();
+        }
+        self.remaining = self.remaining.clone() - T::one();
+        self.current = Some(current.clone().move_one(self.direction));
+        Some(current)
+    }
+}
+
+/// Iterator over the vertices of a path. See [`Path::vertices`].
+#[derive(Debug, Clone)]
+pub struct Vertices<'path, T> {
+    current: Option<Vec2<T>>,
+    steps: slice::Iter<'path, DirecVector<T>>,
+}
+
+impl<'path, T> Iterator for Vertices<'path, T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T>,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.take()?;
+        if let Some(step) = self.steps.next() {
+            self.current = Some(current.clone().move_by(step.clone()));
+        }
+        Some(current)
+    }
+}
@@ -0,0 +1,75 @@
+use super::Path;
+use crate::{
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn random_path(rng: &mut StdRng) -> Path<i32> {
+    let start = Vec2 { x: rng.gen_range(-5 .. 5), y: rng.gen_range(-5 .. 5) };
+    let steps = (0 .. rng.gen_range(0 .. 6))
+        .map(|_| DirecVector {
+            direction: Direction::iter().nth(rng.gen_range(0 .. 4)).unwrap(),
+            magnitude: rng.gen_range(0 .. 4),
+        })
+        .collect();
+    Path::new(start, steps, rng.gen_range(0 .. 30))
+}
+
+#[test]
+fn points_and_vertices() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0 .. 200 {
+        let path = random_path(&mut rng);
+        let points: Vec<_> = path.points().collect();
+        assert_eq!(points.len() as i32, path.length() + 1);
+        assert_eq!(points.first(), Some(&path.start));
+        assert_eq!(points.last(), Some(&path.end()));
+        for pair in points.windows(2) {
+            assert_eq!(pair[0].manhattan_dist::<i32>(pair[1]), 1);
+        }
+
+        let vertices: Vec<_> = path.vertices().collect();
+        assert_eq!(vertices.len(), path.steps.len() + 1);
+        assert_eq!(vertices.last(), Some(&path.end()));
+        assert!(vertices.iter().all(|vertex| points.contains(vertex)));
+    }
+}
+
+#[test]
+fn turns() {
+    let step = |direction, magnitude| DirecVector { direction, magnitude };
+    let path = Path::new(
+        Vec2 { x: 0u8, y: 0 },
+        vec![
+            step(Direction::Right, 2),
+            step(Direction::Right, 1),
+            step(Direction::Down, 3),
+            step(Direction::Left, 1),
+        ],
+        0,
+    );
+    assert_eq!(path.turns(), 2);
+    assert_eq!(path.length(), 7);
+    assert_eq!(path.end(), Vec2 { x: 2, y: 3 });
+    assert_eq!(Path::new(Vec2 { x: 0u8, y: 0 }, vec![], 0).turns(), 0);
+}
+
+#[test]
+fn reverse() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0 .. 200 {
+        let path = random_path(&mut rng);
+        let mut reversed = path.clone();
+        reversed.reverse();
+        assert_eq!(reversed.start, path.end());
+        assert_eq!(reversed.end(), path.start);
+        assert_eq!(reversed.cost, path.cost);
+        assert_eq!(reversed.turns(), path.turns());
+        let mut points: Vec<_> = path.points().collect();
+        points.reverse();
+        assert_eq!(reversed.points().collect::<Vec<_>>(), points);
+        reversed.reverse();
+        assert_eq!(reversed, path);
+    }
+}
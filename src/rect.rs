@@ -3,7 +3,11 @@
 #[cfg(test)]
 mod test;
 
-use crate::{axis::Axis, coord::Vec2};
+use crate::{
+    axis::Axis,
+    coord::{self, Vec2},
+};
+use num::traits::AsPrimitive;
 pub use num::traits::{
     CheckedAdd,
     CheckedSub,
@@ -14,7 +18,13 @@ pub use num::traits::{
     WrappingSub,
     Zero,
 };
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
 
 /// A rectangle in a plane.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -29,6 +39,133 @@ pub struct Rect<T, S = T> {
     pub size: Vec2<S>,
 }
 
+/// Displays the rectangle as a range `start..end` (i.e. end excluded). If the
+/// end is not representable by `T`, the rectangle is displayed as `start+size`
+/// instead, so that the output can always be parsed back.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{rect::Rect, coord::Vec2};
+///
+/// # fn main() {
+/// let rectangle: Rect<u16> = Rect {
+///     start: Vec2 { x: 5, y: 3 },
+///     size: Vec2 { x: 2, y: 6 },
+/// };
+/// assert_eq!(rectangle.to_string(), "(x=5, y=3)..(x=7, y=9)");
+///
+/// let at_bound: Rect<u8> = Rect {
+///     start: Vec2 { x: 200, y: 0 },
+///     size: Vec2 { x: 56, y: 3 },
+/// };
+/// assert_eq!(at_bound.to_string(), "(x=200, y=0)+(x=56, y=3)");
+/// # }
+/// ```
+impl<T> fmt::Display for Rect<T>
+where
+    T: fmt::Display + CheckedAdd,
+{
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self.checked_end() {
+            Some(end) => write!(fmtr, "{}..{}", self.start, end),
+            None => write!(fmtr, "{}+{}", self.start, self.size),
+        }
+    }
+}
+
+/// Error when parsing a rectangle from its display form, i.e. `start..end` or
+/// `start+size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromStrErr<E> {
+    /// Thrown when the separator (`..` or `+`) is missing.
+    MissingSep,
+    /// The start of the range could not be parsed.
+    BadStart(coord::FromStrErr<E>),
+    /// The end of the range could not be parsed.
+    BadEnd(coord::FromStrErr<E>),
+    /// The size could not be parsed.
+    BadSize(coord::FromStrErr<E>),
+    /// The end of the range is before its start, the size is negative or the
+    /// size overflows.
+    InvalidRange,
+}
+
+impl<E> fmt::Display for FromStrErr<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStrErr::MissingSep => {
+                fmt.write_str("Missing separator in rectangle")
+            },
+            FromStrErr::BadStart(err) => {
+                write!(fmt, "Parse error in rectangle start: {}", err)
+            },
+            FromStrErr::BadEnd(err) => {
+                write!(fmt, "Parse error in rectangle end: {}", err)
+            },
+            FromStrErr::BadSize(err) => {
+                write!(fmt, "Parse error in rectangle size: {}", err)
+            },
+            FromStrErr::InvalidRange => {
+                fmt.write_str("Rectangle end is before its start")
+            },
+        }
+    }
+}
+
+impl<E> Error for FromStrErr<E> where E: Error {}
+
+/// Parses a rectangle from the same formats produced by its [`fmt::Display`]
+/// implementation, i.e. `start..end` (end excluded) or `start+size`.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{rect::Rect, coord::Vec2};
+///
+/// # fn main() {
+/// let rectangle: Rect<i16> = Rect {
+///     start: Vec2 { x: -5, y: 3 },
+///     size: Vec2 { x: 2, y: 6 },
+/// };
+/// assert_eq!(rectangle.to_string().parse(), Ok(rectangle));
+/// assert_eq!("(-5, 3)..(-3, 9)".parse(), Ok(rectangle));
+/// assert_eq!("(-5, 3)+(2, 6)".parse(), Ok(rectangle));
+/// # }
+/// ```
+impl<T> FromStr for Rect<T>
+where
+    T: FromStr + CheckedSub + Ord + Clone + Zero,
+{
+    type Err = FromStrErr<T::Err>;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // The start ends at its closing parenthesis, and the separator
+        // follows it.
+        let index = input.find(')').ok_or(FromStrErr::MissingSep)? + 1;
+        let start: Vec2<T> =
+            input[.. index].parse().map_err(FromStrErr::BadStart)?;
+        let rest = input[index ..].trim_start();
+        if let Some(size) = rest.strip_prefix('+') {
+            let size: Vec2<T> = size.parse().map_err(FromStrErr::BadSize)?;
+            if Axis::iter().any(|axis| size[axis] < T::zero()) {
+                return Err(FromStrErr::InvalidRange);
+            }
+            return Ok(Self { start, size });
+        }
+        let end: Vec2<T> = rest
+            .strip_prefix("..")
+            .ok_or(FromStrErr::MissingSep)?
+            .parse()
+            .map_err(FromStrErr::BadEnd)?;
+        if Axis::iter().any(|axis| end[axis] < start[axis]) {
+            return Err(FromStrErr::InvalidRange);
+        }
+        Self::try_from_range(start, end).ok_or(FromStrErr::InvalidRange)
+    }
+}
+
 impl<T, S> Rect<T, S> {
     /// Builds the rectangle from the given range `start .. end` (i.e. end
     /// excluded).
@@ -488,6 +625,45 @@ impl<T> Rect<T> {
     }
 }
 
+impl<T> Rect<T> {
+    /// Tries to convert the start and the size of the rectangle into another
+    /// type, returning an error if some coordinate does not fit in the target
+    /// type.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{rect::Rect, coord::Vec2};
+    ///
+    /// # fn main() {
+    /// let rectangle: Rect<i32> = Rect {
+    ///     start: Vec2 { x: 5, y: 3 },
+    ///     size: Vec2 { x: 2, y: 6 },
+    /// };
+    /// assert_eq!(
+    ///     rectangle.try_cast::<u8>(),
+    ///     Ok(Rect { start: Vec2 { x: 5, y: 3 }, size: Vec2 { x: 2, y: 6 } })
+    /// );
+    /// # }
+    /// ```
+    pub fn try_cast<U>(self) -> Result<Rect<U>, U::Error>
+    where
+        U: TryFrom<T>,
+    {
+        Ok(Rect { start: self.start.try_cast()?, size: self.size.try_cast()? })
+    }
+
+    /// Converts the start and the size of the rectangle into another primitive
+    /// type, with the same semantics of the `as` operator. See
+    /// [`Vec2::cast_lossy`].
+    pub fn cast_lossy<U>(self) -> Rect<U>
+    where
+        T: AsPrimitive<U>,
+        U: Copy + 'static,
+    {
+        Rect { start: self.start.cast_lossy(), size: self.size.cast_lossy() }
+    }
+}
+
 impl<T, S> Rect<T, S> {
     /// Iterator over all coordinates of this rectangle in the direction of
     /// columns.
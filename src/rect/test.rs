@@ -1,4 +1,7 @@
-use crate::{coord::Vec2, rect::Rect};
+use crate::{
+    coord::Vec2,
+    rect::{FromStrErr, Rect},
+};
 
 #[test]
 fn from_range() {
@@ -712,3 +715,95 @@ fn borders() {
         Rect::<u16> { start: Vec2 { x: 1, y: 3 }, size: Vec2 { x: 0, y: 0 } };
     assert_eq!(rect.borders().collect::<Vec<_>>(), Vec::new());
 }
+
+#[test]
+fn try_cast() {
+    let rect: Rect<i32> =
+        Rect { start: Vec2 { x: -5, y: 3 }, size: Vec2 { x: 2, y: 300 } };
+    assert_eq!(
+        rect.try_cast::<i16>(),
+        Ok(Rect { start: Vec2 { x: -5, y: 3 }, size: Vec2 { x: 2, y: 300 } })
+    );
+    assert!(rect.try_cast::<u16>().is_err());
+    assert!(rect.try_cast::<i8>().is_err());
+}
+
+#[test]
+fn cast_lossy() {
+    let rect: Rect<i32> =
+        Rect { start: Vec2 { x: -5, y: 3 }, size: Vec2 { x: 2, y: 300 } };
+    assert_eq!(
+        rect.cast_lossy::<u8>(),
+        Rect { start: Vec2 { x: 251, y: 3 }, size: Vec2 { x: 2, y: 44 } }
+    );
+}
+
+#[test]
+fn display_from_str() {
+    let rect: Rect<i32> =
+        Rect { start: Vec2 { x: -5, y: 3 }, size: Vec2 { x: 2, y: 300 } };
+    assert_eq!(rect.to_string(), "(x=-5, y=3)..(x=-3, y=303)");
+    assert_eq!(rect.to_string().parse(), Ok(rect));
+
+    let empty: Rect<u8> =
+        Rect { start: Vec2 { x: 5, y: 3 }, size: Vec2 { x: 0, y: 0 } };
+    assert_eq!(empty.to_string().parse(), Ok(empty));
+
+    assert_eq!(
+        "(x=5, y=3)(x=6, y=4)".parse::<Rect<u8>>(),
+        Err(FromStrErr::MissingSep)
+    );
+    assert_eq!(
+        "(x=5, y=3)..(x=4, y=4)".parse::<Rect<u8>>(),
+        Err(FromStrErr::InvalidRange)
+    );
+    assert!(matches!(
+        "(x=5, y=3..(x=6, y=4)".parse::<Rect<u8>>(),
+        Err(FromStrErr::BadStart(_))
+    ));
+    assert!(matches!(
+        "(x=5, y=3)..(x=600, y=4)".parse::<Rect<u8>>(),
+        Err(FromStrErr::BadEnd(_))
+    ));
+    assert_eq!(
+        "(x=-100, y=0)..(x=100, y=0)".parse::<Rect<i8>>(),
+        Err(FromStrErr::InvalidRange)
+    );
+    assert!(matches!(
+        "(x=5, y=3)+(x=-1, y=4)".parse::<Rect<u8>>(),
+        Err(FromStrErr::BadSize(_))
+    ));
+    assert_eq!(
+        "(x=5, y=3)+(x=-1, y=4)".parse::<Rect<i8>>(),
+        Err(FromStrErr::InvalidRange)
+    );
+    assert_eq!(
+        "(x=5, y=3)+(x=1, y=-4)".parse::<Rect<i8>>(),
+        Err(FromStrErr::InvalidRange)
+    );
+}
+
+#[test]
+fn display_from_str_at_bound() {
+    let rect: Rect<u8> =
+        Rect { start: Vec2 { x: 200, y: 0 }, size: Vec2 { x: 56, y: 3 } };
+    assert_eq!(rect.to_string(), "(x=200, y=0)+(x=56, y=3)");
+    assert_eq!(rect.to_string().parse(), Ok(rect));
+
+    let rect: Rect<i8> = Rect {
+        start: Vec2 { x: -128, y: -128 },
+        size: Vec2 { x: 127, y: 127 },
+    };
+    assert_eq!(rect.to_string(), "(x=-128, y=-128)..(x=-1, y=-1)");
+    assert_eq!(rect.to_string().parse(), Ok(rect));
+
+    let rect: Rect<i8> =
+        Rect { start: Vec2 { x: 0, y: 127 }, size: Vec2 { x: 1, y: 1 } };
+    assert_eq!(rect.to_string(), "(x=0, y=127)+(x=1, y=1)");
+    assert_eq!(rect.to_string().parse(), Ok(rect));
+
+    let empty: Rect<u8> =
+        Rect { start: Vec2 { x: 255, y: 255 }, size: Vec2 { x: 0, y: 0 } };
+    assert_eq!(empty.to_string(), "(x=255, y=255)..(x=255, y=255)");
+    assert_eq!(empty.to_string().parse(), Ok(empty));
+}
@@ -0,0 +1,374 @@
+//! This module provides incremental path planning with the D* Lite
+//! algorithm, for maps whose obstacles change while paths are followed.
+//!
+//! The search runs backwards, from the goal till the start, and its state is
+//! kept between plans. When some points change (e.g. a door opens or a wall
+//! is built), only the parts of the search affected by them are searched
+//! again. The start may move too, e.g. as a unit follows the path, and the
+//! search is still reused.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+};
+use num::{CheckedAdd, CheckedSub, One, Zero};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+    ops::{Add, AddAssign, Sub},
+};
+
+/// An incremental path planner from a (possibly moving) start till a fixed
+/// goal. Every step into a valid point costs one. As in
+/// [`Graph::make_path`](crate::graph::Graph::make_path), the start does not
+/// need to be valid, and `valid_points` should only accept a bounded region,
+/// otherwise planning might not finish when there is no path.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     coord::Vec2,
+///     direc::{DirecVector, Direction},
+///     replan::PathReplanner,
+/// };
+/// use std::collections::HashSet;
+///
+/// # fn main() {
+/// let mut walls = HashSet::new();
+/// let start = Vec2 { x: 0u8, y: 2 };
+/// let goal = Vec2 { x: 6, y: 2 };
+/// let mut planner = PathReplanner::new(start, goal);
+///
+/// let steps = planner.plan(|point| point.x <= 6 && point.y <= 4);
+/// assert_eq!(
+///     steps,
+///     Some(vec![DirecVector { direction: Direction::Right, magnitude: 6 }])
+/// );
+///
+/// // The unit moves two steps, and a wall is built in front of it.
+/// planner.move_start(Vec2 { x: 2, y: 2 });
+/// walls.extend((1 ..= 4).map(|y| Vec2 { x: 4, y }));
+/// let valid_points = |point: &Vec2<u8>| {
+///     point.x <= 6 && point.y <= 4 && !walls.contains(point)
+/// };
+/// planner.update_cells(walls.iter(), valid_points);
+///
+/// let steps = planner.plan(valid_points);
+/// assert_eq!(
+///     steps,
+///     Some(vec![
+///         DirecVector { direction: Direction::Up, magnitude: 2 },
+///         DirecVector { direction: Direction::Right, magnitude: 4 },
+///         DirecVector { direction: Direction::Down, magnitude: 2 },
+///     ])
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathReplanner<T>
+where
+    T: Clone + Hash + Ord,
+{
+    start: Vec2<T>,
+    last_start: Vec2<T>,
+    goal: Vec2<T>,
+    key_modifier: T,
+    travelled: HashMap<Vec2<T>, T>,
+    lookahead: HashMap<Vec2<T>, T>,
+    queue: BinaryHeap<Reverse<(Key<T>, Vec2<T>)>>,
+    queued: HashMap<Vec2<T>, Key<T>>,
+    expansions: usize,
+}
+
+impl<T> PathReplanner<T>
+where
+    T: Clone + Hash + Ord,
+    T: Zero + One + AddAssign + CheckedAdd + CheckedSub,
+    T: Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates a new planner from the given start till the given goal. No
+    /// search is done till the first plan.
+    pub fn new(start: Vec2<T>, goal: Vec2<T>) -> Self {
+        let mut this = Self {
+            last_start: start.clone(),
+            start,
+            goal: goal.clone(),
+            key_modifier: T::zero(),
+            travelled: HashMap::new(),
+            lookahead: HashMap::new(),
+            queue: BinaryHeap::new(),
+            queued: HashMap::new(),
+            expansions: 0,
+        };
+        this.lookahead.insert(goal.clone(), T::zero());
+        let key = this.key(&goal);
+        this.enqueue(goal, key);
+        this
+    }
+
+    /// The current start of the planner.
+    pub fn start(&self) -> &Vec2<T> {
+        &self.start
+    }
+
+    /// The goal of the planner.
+    pub fn goal(&self) -> &Vec2<T> {
+        &self.goal
+    }
+
+    /// How many points the last plan expanded.
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    /// Moves the start of the planner, e.g. after following a few steps of
+    /// the last plan. The search done so far is kept.
+    pub fn move_start(&mut self, start: Vec2<T>) {
+        self.start = start;
+        let moved = self.last_start.clone().manhattan_dist(self.start.clone());
+        self.key_modifier += moved;
+        self.last_start = self.start.clone();
+    }
+
+    /// Notifies the planner that the given points changed, i.e. they became
+    /// valid or invalid. The function `valid_points` must already reflect
+    /// the changes. The affected parts of the search are searched again in
+    /// the next plan.
+    pub fn update_cells<'points, I, F>(
+        &mut self,
+        changed_points: I,
+        mut valid_points: F,
+    ) where
+        I: IntoIterator<Item = &'points Vec2<T>>,
+        F: FnMut(&Vec2<T>) -> bool,
+        T: 'points,
+    {
+        for point in changed_points {
+            // Steps into a changed point change their cost, so the points
+            // before them must be updated.
+            self.update(point, &mut valid_points);
+            for direction in Direction::iter() {
+                if let Some(neighbour) = point.clone().checked_move(direction) {
+                    self.update(&neighbour, &mut valid_points);
+                }
+            }
+        }
+    }
+
+    /// Plans a path from the current start till the goal, reusing the
+    /// search done by previous plans, and returns its steps. The only points
+    /// used are the ones validated by `valid_points`, which must be the same
+    /// as the one given to the last [`PathReplanner::update_cells`]. Returns
+    /// `None` if there is no path.
+    pub fn plan<F>(
+        &mut self,
+        mut valid_points: F,
+    ) -> Option<Vec<DirecVector<T>>>
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        self.compute(&mut valid_points);
+
+        let total = self.travelled.get(&self.start)?.clone();
+        let mut steps = Vec::<DirecVector<T>>::new();
+        let mut current = self.start.clone();
+        let mut length = T::zero();
+        while current != self.goal {
+            let preferred = steps.last().map(|step| step.direction);
+            let (direction, next, _) =
+                self.best_step(&current, preferred, &mut valid_points)?;
+            length += T::one();
+            // Safeguard against following an inconsistent part of the
+            // search, which could cycle.
+            if length > total {
+                return None;
+            }
+            match steps.last_mut() {
+                Some(step) if step.direction == direction => {
+                    step.magnitude += T::one()
+                },
+                _ => steps.push(DirecVector { direction, magnitude: T::one() }),
+            }
+            current = next;
+        }
+        Some(steps)
+    }
+
+    /// Searches till the start is consistent, i.e. till its distance to the
+    /// goal is known.
+    fn compute<F>(&mut self, valid_points: &mut F)
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        self.expansions = 0;
+        while let Some((old_key, point)) = self.top() {
+            let below_start = match self.key(&self.start) {
+                Some(start_key) => old_key < start_key,
+                None => true,
+            };
+            let start_consistent = self.travelled.get(&self.start)
+                == self.lookahead.get(&self.start);
+            if !below_start && start_consistent {
+                break;
+            }
+            self.queue.pop();
+            self.queued.remove(&point);
+            self.expansions += 1;
+
+            let new_key = self.key(&point);
+            if Some(&old_key) < new_key.as_ref() {
+                self.enqueue(point, new_key);
+            } else if is_less(
+                self.lookahead.get(&point),
+                self.travelled.get(&point),
+            ) {
+                let lookahead = self.lookahead[&point].clone();
+                self.travelled.insert(point.clone(), lookahead);
+                self.update_neighbours(&point, valid_points);
+            } else {
+                self.travelled.remove(&point);
+                self.update(&point, valid_points);
+                self.update_neighbours(&point, valid_points);
+            }
+        }
+    }
+
+    /// Updates the neighbours of the given point.
+    fn update_neighbours<F>(&mut self, point: &Vec2<T>, valid_points: &mut F)
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        for direction in Direction::iter() {
+            if let Some(neighbour) = point.clone().checked_move(direction) {
+                self.update(&neighbour, valid_points);
+            }
+        }
+    }
+
+    /// Recomputes the lookahead distance of the given point from its
+    /// neighbours, and queues it if it became inconsistent.
+    fn update<F>(&mut self, point: &Vec2<T>, valid_points: &mut F)
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        if *point != self.goal {
+            match self.best_step(point, None, valid_points) {
+                Some((_, _, distance)) => {
+                    self.lookahead.insert(point.clone(), distance);
+                },
+                None => {
+                    self.lookahead.remove(point);
+                },
+            }
+        }
+        self.queued.remove(point);
+        if self.travelled.get(point) != self.lookahead.get(point) {
+            let key = self.key(point);
+            self.enqueue(point.clone(), key);
+        }
+    }
+
+    /// Finds the best step from the given point towards the goal, given the
+    /// distances known so far, preferring the given direction among equally
+    /// good steps. Returns the direction of the step, the point after it, and
+    /// the distance to the goal through it.
+    fn best_step<F>(
+        &self,
+        point: &Vec2<T>,
+        preferred: Option<Direction>,
+        valid_points: &mut F,
+    ) -> Option<(Direction, Vec2<T>, T)>
+    where
+        F: FnMut(&Vec2<T>) -> bool,
+    {
+        let mut best: Option<(Direction, Vec2<T>, T)> = None;
+        for direction in Direction::iter() {
+            let neighbour = match point.clone().checked_move(direction) {
+                Some(neighbour) => neighbour,
+                None => continue,
+            };
+            let travelled = match self.travelled.get(&neighbour) {
+                Some(travelled) => travelled.clone(),
+                None => continue,
+            };
+            if !valid_points(&neighbour) {
+                continue;
+            }
+            let distance = travelled + T::one();
+            let is_better = match &best {
+                Some((_, _, best_distance)) => {
+                    distance < *best_distance
+                        || distance == *best_distance
+                            && preferred == Some(direction)
+                },
+                None => true,
+            };
+            if is_better {
+                best = Some((direction, neighbour, distance));
+            }
+        }
+        best
+    }
+
+    /// Computes the key of the given point in the queue. Points with unknown
+    /// distance have no key, which is greater than any key.
+    fn key(&self, point: &Vec2<T>) -> Option<Key<T>> {
+        let distance =
+            match (self.travelled.get(point), self.lookahead.get(point)) {
+                (Some(travelled), Some(lookahead)) => travelled.min(lookahead),
+                (Some(distance), None) | (None, Some(distance)) => distance,
+                (None, None) => return None,
+            };
+        let estimate = distance.clone()
+            + point.clone().manhattan_dist(self.start.clone())
+            + self.key_modifier.clone();
+        Some(Key { estimate, distance: distance.clone() })
+    }
+
+    /// Queues the given point with the given key, replacing its previous
+    /// key.
+    fn enqueue(&mut self, point: Vec2<T>, key: Option<Key<T>>) {
+        if let Some(key) = key {
+            self.queued.insert(point.clone(), key.clone());
+            self.queue.push(Reverse((key, point)));
+        }
+    }
+
+    /// Returns the point with the smallest key in the queue, together with
+    /// its key, without removing it. Stale entries of the queue, whose point
+    /// was queued again or removed, are discarded.
+    fn top(&mut self) -> Option<(Key<T>, Vec2<T>)> {
+        while let Some(Reverse((key, point))) = self.queue.peek() {
+            if self.queued.get(point) == Some(key) {
+                return Some((key.clone(), point.clone()));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+}
+
+/// The key of a point in the queue of the planner. Ordered by the estimated
+/// cost of a path through the point, then by the distance to the goal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Key<T> {
+    estimate: T,
+    distance: T,
+}
+
+/// Compares two distances, where `None` stands for an unknown (infinite)
+/// distance.
+fn is_less<T>(left: Option<&T>, right: Option<&T>) -> bool
+where
+    T: Ord,
+{
+    match (left, right) {
+        (Some(left), Some(right)) => left < right,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
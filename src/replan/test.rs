@@ -0,0 +1,112 @@
+use super::PathReplanner;
+use crate::{
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+    graph::Graph,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::BTreeSet;
+
+fn random_point(rng: &mut StdRng) -> Vec2<i32> {
+    Vec2 { x: rng.gen_range(0 .. 15), y: rng.gen_range(0 .. 15) }
+}
+
+/// Checks the planned steps against a path made from scratch.
+fn assert_plan<F>(
+    steps: &Option<Vec<DirecVector<i32>>>,
+    start: Vec2<i32>,
+    goal: Vec2<i32>,
+    mut valid_points: F,
+) where
+    F: FnMut(&Vec2<i32>) -> bool,
+{
+    let expected = Graph::new().find_path(&start, &goal, &0, &mut valid_points);
+    match (steps, expected) {
+        (Some(steps), Some(expected)) => {
+            let mut point = start;
+            for step in steps {
+                for _ in 0 .. step.magnitude {
+                    point = point.move_one(step.direction);
+                    assert!(valid_points(&point));
+                }
+            }
+            assert_eq!(point, goal);
+            let length: i32 = steps.iter().map(|step| step.magnitude).sum();
+            assert_eq!(length, expected.length());
+        },
+        (None, None) => (),
+        (steps, expected) => panic!("{:?} != {:?}", steps, expected),
+    }
+}
+
+#[test]
+fn plan_and_update() {
+    let mut rng = StdRng::seed_from_u64(17);
+    let mut replanned_expansions = 0;
+    let mut fresh_expansions = 0;
+    for _ in 0 .. 30 {
+        let mut walls: BTreeSet<_> =
+            (0 .. 50).map(|_| random_point(&mut rng)).collect();
+        let start = random_point(&mut rng);
+        let goal = random_point(&mut rng);
+        walls.remove(&goal);
+        let mut planner = PathReplanner::new(start, goal);
+        for _ in 0 .. 10 {
+            let valid_points = |point: &Vec2<i32>| {
+                (0 .. 15).contains(&point.x)
+                    && (0 .. 15).contains(&point.y)
+                    && !walls.contains(point)
+            };
+            let steps = planner.plan(valid_points);
+            assert_plan(&steps, *planner.start(), goal, valid_points);
+
+            // Follows the first step, if any, and changes a few points.
+            if let Some(step) = steps.as_ref().and_then(|steps| steps.first()) {
+                let next = planner.start().move_one(step.direction);
+                planner.move_start(next);
+            }
+            let changed: Vec<_> = (0 .. 3)
+                .map(|_| random_point(&mut rng))
+                .filter(|point| point != &goal)
+                .collect();
+            for point in &changed {
+                if !walls.remove(point) {
+                    walls.insert(*point);
+                }
+            }
+            let valid_points = |point: &Vec2<i32>| {
+                (0 .. 15).contains(&point.x)
+                    && (0 .. 15).contains(&point.y)
+                    && !walls.contains(point)
+            };
+            planner.update_cells(&changed, valid_points);
+            let steps = planner.plan(valid_points);
+            assert_plan(&steps, *planner.start(), goal, valid_points);
+            replanned_expansions += planner.expansions();
+
+            let mut fresh = PathReplanner::new(*planner.start(), goal);
+            let fresh_steps = fresh.plan(valid_points);
+            assert_eq!(steps.is_some(), fresh_steps.is_some());
+            fresh_expansions += fresh.expansions();
+        }
+    }
+    assert!(replanned_expansions * 2 < fresh_expansions);
+}
+
+#[test]
+fn start_is_goal() {
+    let valid_points = |point: &Vec2<u8>| point.y < 5;
+    let goal = Vec2 { x: 3, y: 3 };
+    let mut planner = PathReplanner::new(goal, goal);
+    assert_eq!(planner.plan(valid_points), Some(Vec::new()));
+    planner.move_start(Vec2 { x: 3, y: 4 });
+    assert_eq!(
+        planner.plan(valid_points),
+        Some(vec![DirecVector { direction: Direction::Up, magnitude: 1 }])
+    );
+    planner.move_start(Vec2 { x: 3, y: 0 });
+    assert_eq!(
+        planner.plan(valid_points),
+        Some(vec![DirecVector { direction: Direction::Down, magnitude: 3 }])
+    );
+}
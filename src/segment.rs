@@ -0,0 +1,424 @@
+//! This module provides axis-aligned segments.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    axis::Axis,
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+};
+use num::{One, Zero};
+use std::ops::{Add, Sub};
+
+/// An axis-aligned segment in a plane, starting at a point and moving by a
+/// straight vector. Both the start and the end are part of the segment. A
+/// segment with zero magnitude is a single point. The end of the segment is
+/// assumed to be representable by `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Segment<T> {
+    /// Starting point.
+    pub start: Vec2<T>,
+    /// Vector from the start to the end.
+    pub vector: DirecVector<T>,
+}
+
+/// Intersection of two segments. See [`Segment::intersection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Intersection<T> {
+    /// The segments meet at a single point.
+    Point(Vec2<T>),
+    /// The segments are collinear and share more than one point.
+    Overlap(Segment<T>),
+}
+
+impl<T> Segment<T> {
+    /// Creates a segment from its start and its vector.
+    pub fn new(start: Vec2<T>, vector: DirecVector<T>) -> Self {
+        Self { start, vector }
+    }
+
+    /// Creates a segment from its start and its end, returning `None` if they
+    /// are not in a straight line. If both points are equal, the segment has
+    /// zero magnitude and points to the right.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let segment = Segment::from_ends(Vec2 { x: 3u16, y: 9 }, Vec2 { x: 3, y: 4 });
+    /// assert_eq!(
+    ///     segment,
+    ///     Some(Segment {
+    ///         start: Vec2 { x: 3, y: 9 },
+    ///         vector: DirecVector { direction: Direction::Up, magnitude: 5 },
+    ///     })
+    /// );
+    /// assert_eq!(Segment::from_ends(Vec2 { x: 3, y: 9 }, Vec2 { x: 4, y: 4 }), None);
+    /// # }
+    /// ```
+    pub fn from_ends(start: Vec2<T>, end: Vec2<T>) -> Option<Self>
+    where
+        T: Ord + Clone + Sub<Output = T> + Zero,
+    {
+        if start == end {
+            let vector = DirecVector {
+                direction: Direction::Right,
+                magnitude: T::zero(),
+            };
+            return Some(Self { start, vector });
+        }
+        let direction = start.direction_to(&end)?;
+        let axis = direction.axis();
+        let (low, high) = match direction {
+            Direction::Down | Direction::Right => (&start, &end),
+            Direction::Up | Direction::Left => (&end, &start),
+        };
+        let magnitude = high[axis].clone() - low[axis].clone();
+        Some(Self { start, vector: DirecVector { direction, magnitude } })
+    }
+
+    /// The axis along which this segment is aligned.
+    pub fn axis(&self) -> Axis {
+        self.vector.direction.axis()
+    }
+
+    /// Computes the end of this segment.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let segment = Segment::new(
+    ///     Vec2 { x: -2i32, y: 5 },
+    ///     DirecVector { direction: Direction::Left, magnitude: 4 },
+    /// );
+    /// assert_eq!(segment.end(), Vec2 { x: -6, y: 5 });
+    /// # }
+    /// ```
+    pub fn end(&self) -> Vec2<T>
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        self.start.clone().move_by(self.vector.clone())
+    }
+
+    /// Returns the same segment, but starting at its smallest point, i.e.
+    /// pointing right or down.
+    pub fn normalized(&self) -> Self
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        match self.vector.direction {
+            Direction::Down | Direction::Right => self.clone(),
+            Direction::Up | Direction::Left => Self {
+                start: self.end(),
+                vector: DirecVector {
+                    direction: !self.vector.direction,
+                    magnitude: self.vector.magnitude.clone(),
+                },
+            },
+        }
+    }
+
+    /// Returns the same segment, but starting at its end and ending at its
+    /// start.
+    pub fn reversed(&self) -> Self
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        Self {
+            start: self.end(),
+            vector: DirecVector {
+                direction: !self.vector.direction,
+                magnitude: self.vector.magnitude.clone(),
+            },
+        }
+    }
+
+    /// Smallest and greatest points of this segment.
+    fn bounds(&self) -> (Vec2<T>, Vec2<T>)
+    where
+        T: Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        let normalized = self.normalized();
+        let end = normalized.end();
+        (normalized.start, end)
+    }
+
+    /// Tests whether the given point is in this segment.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let segment = Segment::new(
+    ///     Vec2 { x: 2u8, y: 5 },
+    ///     DirecVector { direction: Direction::Up, magnitude: 3 },
+    /// );
+    /// assert!(segment.contains(&Vec2 { x: 2, y: 5 }));
+    /// assert!(segment.contains(&Vec2 { x: 2, y: 3 }));
+    /// assert!(segment.contains(&Vec2 { x: 2, y: 2 }));
+    /// assert!(!segment.contains(&Vec2 { x: 2, y: 1 }));
+    /// assert!(!segment.contains(&Vec2 { x: 3, y: 3 }));
+    /// # }
+    /// ```
+    pub fn contains(&self, point: &Vec2<T>) -> bool
+    where
+        T: Ord + Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        let (low, high) = self.bounds();
+        Axis::iter()
+            .all(|axis| low[axis] <= point[axis] && point[axis] <= high[axis])
+    }
+
+    /// Iterator over the points of this segment, from the start to the end.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let segment = Segment::new(
+    ///     Vec2 { x: 2u8, y: 5 },
+    ///     DirecVector { direction: Direction::Left, magnitude: 2 },
+    /// );
+    /// let points: Vec<_> = segment.points().collect();
+    /// assert_eq!(
+    ///     points,
+    ///     vec![Vec2 { x: 2, y: 5 }, Vec2 { x: 1, y: 5 }, Vec2 { x: 0, y: 5 }]
+    /// );
+    /// # }
+    /// ```
+    pub fn points(&self) -> Points<T>
+    where
+        T: Clone,
+    {
+        Points {
+            current: Some(self.start.clone()),
+            remaining: self.vector.magnitude.clone(),
+            direction: self.vector.direction,
+        }
+    }
+
+    /// Computes the intersection of two segments, returning `None` if they do
+    /// not meet. Segments sharing more than one point are collinear, and their
+    /// common part is returned as an [`Intersection::Overlap`], with the same
+    /// direction as `self`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::{Intersection, Segment},
+    /// };
+    ///
+    /// # fn main() {
+    /// let horizontal = Segment::new(
+    ///     Vec2 { x: 0i32, y: 3 },
+    ///     DirecVector { direction: Direction::Right, magnitude: 6 },
+    /// );
+    /// let vertical = Segment::new(
+    ///     Vec2 { x: 4, y: 5 },
+    ///     DirecVector { direction: Direction::Up, magnitude: 4 },
+    /// );
+    /// let collinear = Segment::new(
+    ///     Vec2 { x: 9, y: 3 },
+    ///     DirecVector { direction: Direction::Left, magnitude: 5 },
+    /// );
+    /// assert_eq!(
+    ///     horizontal.intersection(&vertical),
+    ///     Some(Intersection::Point(Vec2 { x: 4, y: 3 }))
+    /// );
+    /// assert_eq!(
+    ///     horizontal.intersection(&collinear),
+    ///     Some(Intersection::Overlap(Segment::new(
+    ///         Vec2 { x: 4, y: 3 },
+    ///         DirecVector { direction: Direction::Right, magnitude: 2 },
+    ///     )))
+    /// );
+    /// # }
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Intersection<T>>
+    where
+        T: Ord + Clone + Add<Output = T> + Sub<Output = T> + Zero,
+    {
+        let overlap = self.overlap(other)?;
+        if overlap.vector.magnitude.is_zero() {
+            Some(Intersection::Point(overlap.start))
+        } else {
+            Some(Intersection::Overlap(overlap))
+        }
+    }
+
+    /// Computes the common part of two segments, with the same direction as
+    /// `self`, returning `None` if they do not meet. This is mostly useful for
+    /// collinear segments, since the common part of segments in different
+    /// axes has zero magnitude.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let first = Segment::new(
+    ///     Vec2 { x: 5u16, y: 1 },
+    ///     DirecVector { direction: Direction::Down, magnitude: 7 },
+    /// );
+    /// let second = Segment::new(
+    ///     Vec2 { x: 5, y: 3 },
+    ///     DirecVector { direction: Direction::Down, magnitude: 9 },
+    /// );
+    /// assert_eq!(
+    ///     first.overlap(&second),
+    ///     Some(Segment::new(
+    ///         Vec2 { x: 5, y: 3 },
+    ///         DirecVector { direction: Direction::Down, magnitude: 5 },
+    ///     ))
+    /// );
+    /// # }
+    /// ```
+    pub fn overlap(&self, other: &Self) -> Option<Self>
+    where
+        T: Ord + Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        let (self_low, self_high) = self.bounds();
+        let (other_low, other_high) = other.bounds();
+        let low = self_low.zip_with(other_low, Ord::max);
+        let high = self_high.zip_with(other_high, Ord::min);
+        if Axis::iter().any(|axis| low[axis] > high[axis]) {
+            return None;
+        }
+
+        let axis = self.axis();
+        let magnitude = high[axis].clone() - low[axis].clone();
+        let direction = self.vector.direction;
+        let start = match direction {
+            Direction::Down | Direction::Right => low,
+            Direction::Up | Direction::Left => high,
+        };
+        Some(Self { start, vector: DirecVector { direction, magnitude } })
+    }
+
+    /// Splits this segment at the given point, returning `None` if the point
+    /// is not in this segment. The first segment goes from the start to the
+    /// point, and the second one goes from the point to the end, both with the
+    /// same direction as `self`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{
+    ///     coord::Vec2,
+    ///     direc::{DirecVector, Direction},
+    ///     segment::Segment,
+    /// };
+    ///
+    /// # fn main() {
+    /// let segment = Segment::new(
+    ///     Vec2 { x: 5u16, y: 1 },
+    ///     DirecVector { direction: Direction::Right, magnitude: 7 },
+    /// );
+    /// assert_eq!(
+    ///     segment.split_at(&Vec2 { x: 7, y: 1 }),
+    ///     Some((
+    ///         Segment::new(
+    ///             Vec2 { x: 5, y: 1 },
+    ///             DirecVector { direction: Direction::Right, magnitude: 2 },
+    ///         ),
+    ///         Segment::new(
+    ///             Vec2 { x: 7, y: 1 },
+    ///             DirecVector { direction: Direction::Right, magnitude: 5 },
+    ///         ),
+    ///     ))
+    /// );
+    /// assert_eq!(segment.split_at(&Vec2 { x: 7, y: 2 }), None);
+    /// # }
+    /// ```
+    pub fn split_at(&self, point: &Vec2<T>) -> Option<(Self, Self)>
+    where
+        T: Ord + Clone + Add<Output = T> + Sub<Output = T>,
+    {
+        if !self.contains(point) {
+            return None;
+        }
+
+        let axis = self.axis();
+        let direction = self.vector.direction;
+        let (first, second) = match direction {
+            Direction::Down | Direction::Right => (
+                point[axis].clone() - self.start[axis].clone(),
+                self.end()[axis].clone() - point[axis].clone(),
+            ),
+            Direction::Up | Direction::Left => (
+                self.start[axis].clone() - point[axis].clone(),
+                point[axis].clone() - self.end()[axis].clone(),
+            ),
+        };
+        Some((
+            Self {
+                start: self.start.clone(),
+                vector: DirecVector { direction, magnitude: first },
+            },
+            Self {
+                start: point.clone(),
+                vector: DirecVector { direction, magnitude: second },
+            },
+        ))
+    }
+}
+
+/// Iterator over the points of a segment. See [`Segment::points`].
+#[derive(Debug, Clone)]
+pub struct Points<T> {
+    current: Option<Vec2<T>>,
+    remaining: T,
+    direction: Direction,
+}
+
+impl<T> Iterator for Points<T>
+where
+    T: Clone + Add<Output = T> + Sub<Output = T> + Zero + One,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.take()?;
+        if !self.remaining.is_zero() {
+            self.remaining = self.remaining.clone() - T::one();
+            self.current = Some(current.clone().move_one(self.direction));
+        }
+        Some(current)
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Intersection, Segment};
+use crate::{
+    coord::Vec2,
+    direc::{DirecVector, Direction},
+};
+use std::collections::BTreeSet;
+
+/// All segments with both ends in the square `[-3, 3]`.
+fn segments() -> Vec<Segment<i32>> {
+    let mut segments = Vec::new();
+    for y in -3i32 ..= 3 {
+        for x in -3 ..= 3 {
+            let start = Vec2 { x, y };
+            for direction in Direction::iter() {
+                for magnitude in 0 ..= 3 {
+                    let segment = Segment::new(
+                        start,
+                        DirecVector { direction, magnitude },
+                    );
+                    let end = segment.end();
+                    if end.x.abs() <= 3 && end.y.abs() <= 3 {
+                        segments.push(segment);
+                    }
+                }
+            }
+        }
+    }
+    segments
+}
+
+#[test]
+fn points_and_contains() {
+    for segment in segments() {
+        let points: Vec<_> = segment.points().collect();
+        assert_eq!(points.len() as i32, segment.vector.magnitude + 1);
+        assert_eq!(points.first(), Some(&segment.start));
+        assert_eq!(points.last(), Some(&segment.end()));
+        for pair in points.windows(2) {
+            assert_eq!(
+                pair[0].direction_to(&pair[1]),
+                Some(segment.vector.direction)
+            );
+        }
+        for y in -4 ..= 4 {
+            for x in -4 ..= 4 {
+                let point = Vec2 { x, y };
+                assert_eq!(segment.contains(&point), points.contains(&point));
+            }
+        }
+    }
+}
+
+#[test]
+fn from_ends() {
+    for segment in segments() {
+        let built = Segment::from_ends(segment.start, segment.end()).unwrap();
+        if segment.vector.magnitude == 0 {
+            assert_eq!(built.start, segment.start);
+            assert_eq!(built.vector.magnitude, 0);
+        } else {
+            assert_eq!(built, segment);
+        }
+        assert_eq!(built.reversed().normalized(), built.normalized());
+    }
+    assert_eq!(
+        Segment::from_ends(Vec2 { x: 0, y: 0 }, Vec2 { x: 1, y: 1 }),
+        None
+    );
+}
+
+#[test]
+fn intersection_matches_points() {
+    let segments = segments();
+    for first in &segments {
+        let first_points: BTreeSet<_> = first.points().collect();
+        for second in &segments {
+            let common: BTreeSet<_> = second
+                .points()
+                .filter(|point| first_points.contains(point))
+                .collect();
+            match first.intersection(second) {
+                None => assert!(common.is_empty()),
+                Some(Intersection::Point(point)) => {
+                    assert_eq!(common, Some(point).into_iter().collect())
+                },
+                Some(Intersection::Overlap(overlap)) => {
+                    assert_eq!(
+                        overlap.vector.direction,
+                        first.vector.direction
+                    );
+                    assert_eq!(common, overlap.points().collect());
+                },
+            }
+        }
+    }
+}
+
+#[test]
+fn split_at() {
+    for segment in segments() {
+        for (index, point) in segment.points().enumerate() {
+            let (first, second) = segment.split_at(&point).unwrap();
+            assert_eq!(first.start, segment.start);
+            assert_eq!(first.end(), point);
+            assert_eq!(second.start, point);
+            assert_eq!(second.end(), segment.end());
+            assert_eq!(first.vector.magnitude, index as i32);
+        }
+        let outside = Vec2 { x: 4, y: 4 };
+        assert_eq!(segment.split_at(&outside), None);
+    }
+}
+
+#[test]
+fn unsigned_bounds() {
+    let segment = Segment::new(
+        Vec2 { x: 0u8, y: 255 },
+        DirecVector { direction: Direction::Right, magnitude: 255 },
+    );
+    assert_eq!(segment.points().count(), 256);
+    assert_eq!(segment.end(), Vec2 { x: 255, y: 255 });
+    let reversed = segment.reversed();
+    assert_eq!(reversed.points().last(), Some(Vec2 { x: 0, y: 255 }));
+    assert_eq!(reversed.normalized(), segment);
+}
@@ -4,8 +4,20 @@
 #[cfg(test)]
 mod test;
 
-use crate::{coord::Vec2, direc::Direction, map, map::Map};
-use std::{borrow::Borrow, iter::FromIterator};
+use crate::{
+    bits::{ExcessToSigned, HalfExcess, UnsignedOffset},
+    coord::Vec2,
+    direc::Direction,
+    map,
+    map::Map,
+    shape::{Area, Metric, Ring},
+};
+use num::{integer::Roots, Bounded, One, Unsigned};
+use std::{
+    borrow::Borrow,
+    iter::FromIterator,
+    ops::{Add, Neg, Sub},
+};
 
 /// The set of coordinates/vectors in a plane, optimized for being in the plane.
 /// Members of the set are `Vec2<T>`.
@@ -91,6 +103,38 @@ where
         Neighbours { inner: self.inner.neighbours_incl(point, direction) }
     }
 
+    /// Returns an iterator to the points in a straight line from a given
+    /// point in the given direction. Unlike [`Set::neighbours`], the starting
+    /// point does not need to be in the set. The starting point is NOT
+    /// included.
+    pub fn neighbours_from<U>(
+        &self,
+        point: Vec2<&U>,
+        direction: Direction,
+    ) -> Neighbours<'_, T>
+    where
+        T: Borrow<U>,
+        U: Ord,
+    {
+        Neighbours { inner: self.inner.neighbours_from(point, direction) }
+    }
+
+    /// Returns an iterator to the points in a straight line from a given
+    /// point in the given direction. Unlike [`Set::neighbours_incl`], the
+    /// starting point does not need to be in the set. The starting point IS
+    /// included, if it is in the set.
+    pub fn neighbours_from_incl<U>(
+        &self,
+        point: Vec2<&U>,
+        direction: Direction,
+    ) -> Neighbours<'_, T>
+    where
+        T: Borrow<U>,
+        U: Ord,
+    {
+        Neighbours { inner: self.inner.neighbours_from_incl(point, direction) }
+    }
+
     /// Returns the nearest neighbour in a straight line of a given point in the
     /// the given direction.
     pub fn first_neighbour<U>(
@@ -151,6 +195,120 @@ where
     pub fn columns(&self) -> Columns<T> {
         Columns { inner: self.inner.columns() }
     }
+
+    /// Builds a new set by converting every point with the given function.
+    pub fn map_points<F, U>(&self, mapper: F) -> Set<U>
+    where
+        F: FnMut(Vec2<&T>) -> Vec2<U>,
+        U: Ord + Clone,
+    {
+        Set { inner: self.inner.map_keys(mapper) }
+    }
+
+    /// Builds a new set by converting every point with the given fallible
+    /// function. The first error found (in the direction of rows) is returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use gardiz::{coord::Vec2, set::Set};
+    ///
+    /// # fn main() {
+    /// let mut set = Set::<u16>::new();
+    /// set.insert(Vec2 { x: 5, y: 3 });
+    /// set.insert(Vec2 { x: 1, y: 9 });
+    ///
+    /// let converted = set.try_map_points(|point| point.copied().try_cast());
+    /// let converted: Set<i8> = converted.unwrap();
+    /// assert!(converted.contains(Vec2 { x: 5, y: 3 }.as_ref()));
+    /// assert!(converted.contains(Vec2 { x: 1, y: 9 }.as_ref()));
+    /// # }
+    /// ```
+    pub fn try_map_points<F, U, E>(&self, mapper: F) -> Result<Set<U>, E>
+    where
+        F: FnMut(Vec2<&T>) -> Result<Vec2<U>, E>,
+        U: Ord + Clone,
+    {
+        Ok(Set { inner: self.inner.try_map_keys(mapper)? })
+    }
+
+    /// Converts every point from an "excess of N" number into a 2's complement
+    /// number. See [`ExcessToSigned`].
+    pub fn excess_to_signed<U>(&self, excess: &Vec2<T>) -> Set<U>
+    where
+        T: Clone,
+        Vec2<T>: ExcessToSigned<Target = Vec2<U>>,
+        U: Ord + Clone,
+    {
+        Set { inner: self.inner.excess_to_signed(excess) }
+    }
+
+    /// Converts every point from an "excess of N" number into a 2's complement
+    /// number, where `N` is half the maximum value of the unsigned point type.
+    /// See [`ExcessToSigned`].
+    pub fn half_exc_to_signed<U>(&self) -> Set<U>
+    where
+        T: Clone,
+        Vec2<T>: ExcessToSigned<Target = Vec2<U>> + HalfExcess,
+        U: Ord + Clone,
+    {
+        Set { inner: self.inner.half_exc_to_signed() }
+    }
+
+    /// Re-centers every point at the given origin and flips its Y coordinate.
+    /// See [`Vec2::center_origin_at`].
+    pub fn center_origin_at<U>(&self, origin: &Vec2<T>) -> Set<U>
+    where
+        T: Clone,
+        Vec2<T>: ExcessToSigned<Target = Vec2<U>>,
+        U: Sub<Output = U> + Neg<Output = U> + One + Ord + Clone,
+    {
+        Set { inner: self.inner.center_origin_at(origin) }
+    }
+
+    /// Re-centers every point at half the maximum value of the unsigned point
+    /// type and flips its Y coordinate. See [`Vec2::center_origin`].
+    pub fn center_origin<U>(&self) -> Set<U>
+    where
+        T: Clone,
+        Vec2<T>: ExcessToSigned<Target = Vec2<U>> + HalfExcess,
+        U: Sub<Output = U> + Neg<Output = U> + One + Ord + Clone,
+    {
+        Set { inner: self.inner.center_origin() }
+    }
+}
+
+impl<T, D> Set<T>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    /// Creates a set with the points at exactly the given distance from the
+    /// center, using the given metric. See [`Ring::new`].
+    pub fn ring(center: Vec2<T>, radius: D, metric: Metric) -> Self {
+        Ring::new(center, radius, metric).collect()
+    }
+
+    /// Creates a set with the points of the outline of a circle. See
+    /// [`Ring::circle`].
+    pub fn circle(center: Vec2<T>, radius: D) -> Self {
+        Ring::circle(center, radius).collect()
+    }
+
+    /// Creates a set with the points at most at the given distance from the
+    /// center, using the given metric. See [`Area::new`].
+    pub fn area(center: Vec2<T>, radius: D, metric: Metric) -> Self {
+        Area::new(center, radius, metric).collect()
+    }
+
+    /// Creates a set with the points of a filled disc. See [`Area::disc`].
+    pub fn disc(center: Vec2<T>, radius: D) -> Self {
+        Area::disc(center, radius).collect()
+    }
 }
 
 impl<T> Extend<Vec2<T>> for Set<T>
@@ -178,7 +336,8 @@ where
 }
 
 /// Iterator over the neighbours of a given point in a given direction (in a
-/// set). See [`Set::neighbours`] and [`Set::neighbours_incl`].
+/// set). See [`Set::neighbours`], [`Set::neighbours_incl`],
+/// [`Set::neighbours_from`] and [`Set::neighbours_from_incl`].
 #[derive(Debug, Clone)]
 pub struct Neighbours<'set, T>
 where
@@ -1,5 +1,5 @@
 use super::Set;
-use crate::{coord::Vec2, direc::Direction};
+use crate::{coord::Vec2, direc::Direction, shape::Metric};
 
 #[test]
 fn insert() {
@@ -349,3 +349,69 @@ fn columns_empty() {
     let columns = set.columns().map(|elem| elem.copied()).collect::<Vec<_>>();
     assert_eq!(columns, &[]);
 }
+
+#[test]
+fn try_map_points() {
+    let mut set = Set::<u16>::new();
+    set.insert(Vec2 { x: 9, y: 12 });
+    set.insert(Vec2 { x: 8, y: 17 });
+
+    let converted =
+        set.try_map_points(|point| point.copied().try_cast::<i8>()).unwrap();
+    assert_eq!(converted.len(), 2);
+    assert!(converted.contains(Vec2 { x: 9, y: 12 }.as_ref()));
+    assert!(converted.contains(Vec2 { x: 8, y: 17 }.as_ref()));
+
+    set.insert(Vec2 { x: 8, y: 1700 });
+    assert!(set
+        .try_map_points(|point| point.copied().try_cast::<i8>())
+        .is_err());
+
+    let mapped = set.map_points(|point| Vec2 { x: *point.x, y: 0 });
+    assert_eq!(mapped.len(), 2);
+    assert!(mapped.contains(Vec2 { x: 8, y: 0 }.as_ref()));
+    assert!(mapped.contains(Vec2 { x: 9, y: 0 }.as_ref()));
+}
+
+#[test]
+fn center_origin() {
+    let mut set = Set::<u8>::new();
+    set.insert(Vec2 { x: 3, y: 130 });
+    set.insert(Vec2 { x: 128, y: 127 });
+
+    let centered = set.center_origin();
+    assert_eq!(centered.len(), 2);
+    assert!(centered.contains(Vec2 { x: -125, y: -3 }.as_ref()));
+    assert!(centered.contains(Vec2 { x: 0, y: 0 }.as_ref()));
+
+    let centered = set.center_origin_at(&Vec2 { x: 20, y: 30 });
+    assert!(centered.contains(Vec2 { x: -17, y: -101 }.as_ref()));
+
+    let signed = set.half_exc_to_signed();
+    assert!(signed.contains(Vec2 { x: -125, y: 2 }.as_ref()));
+    assert!(signed.contains(Vec2 { x: 0, y: -1 }.as_ref()));
+
+    let signed = set.excess_to_signed(&Vec2 { x: 3, y: 3 });
+    assert!(signed.contains(Vec2 { x: 0, y: 127 }.as_ref()));
+}
+
+#[test]
+fn shapes() {
+    let center = Vec2 { x: 5i32, y: -2 };
+
+    let ring = Set::ring(center, 2, Metric::Manhattan);
+    assert_eq!(ring.len(), 8);
+    assert!(ring.contains(Vec2 { x: 6, y: -1 }.as_ref()));
+    assert!(!ring.contains(center.as_ref()));
+
+    let area = Set::area(center, 2, Metric::Chebyshev);
+    assert_eq!(area.len(), 25);
+    assert!(area.contains(Vec2 { x: 7, y: -4 }.as_ref()));
+
+    let circle = Set::circle(center, 3);
+    let disc = Set::disc(center, 3);
+    assert!(circle.rows().all(|point| disc.contains(point)));
+    assert!(!circle.contains(center.as_ref()));
+    assert!(disc.contains(center.as_ref()));
+    assert_eq!(disc.len(), 37);
+}
@@ -0,0 +1,1312 @@
+//! This module provides rasterization of non-rectangular shapes around a
+//! center point: rings and areas in the grid metrics (Manhattan "diamonds" and
+//! Chebyshev "squares"), as well as circles and discs.
+
+#[cfg(test)]
+mod test;
+
+use crate::{
+    axis::Axis,
+    bits::{UnsignedDistance, UnsignedOffset},
+    coord::Vec2,
+    direc::Direction,
+    rect::Rect,
+};
+use num::{
+    integer::Roots,
+    traits::{SaturatingAdd, SaturatingSub},
+    Bounded,
+    CheckedAdd,
+    One,
+    Unsigned,
+    Zero,
+};
+use std::ops::{Add, Sub};
+
+/// A grid metric, i.e. a way of measuring distance between two points such
+/// that points at the same distance from a center form a closed ring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "impl-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Metric {
+    /// Sum of the distances in each axis, see [`Vec2::manhattan_dist`]. Rings
+    /// are "diamonds".
+    Manhattan,
+    /// Maximum of the distances in each axis, see [`Vec2::chebyshev_dist`].
+    /// Rings are "squares".
+    Chebyshev,
+}
+
+/// Iterator over the outline of a shape with a given radius around a center.
+/// See [`Ring::new`] and [`Ring::circle`].
+///
+/// Points are yielded in row-major order (i.e. sorted by `y`, then by `x`).
+/// Points that would not be representable by the coordinate type are skipped,
+/// and no arithmetic can overflow.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{
+///     coord::Vec2,
+///     shape::{Metric, Ring},
+/// };
+///
+/// # fn main() {
+/// let center = Vec2 { x: 3u8, y: 1 };
+/// let points: Vec<_> = Ring::new(center, 1, Metric::Manhattan).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 3, y: 0 },
+///         Vec2 { x: 2, y: 1 },
+///         Vec2 { x: 4, y: 1 },
+///         Vec2 { x: 3, y: 2 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ring<T, D = <T as UnsignedDistance>::Output>
+where
+    T: UnsignedDistance<Output = D>,
+{
+    inner: Raster<T, D>,
+}
+
+impl<T, D> Ring<T, D>
+where
+    T: UnsignedOffset<Output = D> + Bounded,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    /// Creates an iterator over the points at exactly the given distance from
+    /// the center, using the given metric.
+    pub fn new(center: Vec2<T>, radius: D, metric: Metric) -> Self {
+        Self { inner: Raster::new(center, radius, Norm::Metric(metric), true) }
+    }
+
+    /// Creates an iterator over the outline of a circle with the given radius,
+    /// i.e. the points of [`Area::disc`] with the same center and radius that
+    /// have a horizontal or vertical neighbour outside of the disc. The
+    /// outline is 8-connected. Unlike the midpoint circle algorithm, it keeps
+    /// the points where the outline turns diagonally, e.g. it has 8 points for
+    /// radius 1, where the midpoint circle has 4.
+    pub fn circle(center: Vec2<T>, radius: D) -> Self {
+        Self { inner: Raster::new(center, radius, Norm::Euclid, true) }
+    }
+
+    /// Restricts the iterator to the points inside of the given rectangle.
+    pub fn clip_to(mut self, rect: &Rect<T>) -> Self
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero + Ord + Clone,
+    {
+        self.inner.clip_to(rect);
+        self
+    }
+}
+
+impl<T, D> Iterator for Ring<T, D>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterator over all points of a shape with a given radius around a center,
+/// including its interior. See [`Area::new`] and [`Area::disc`].
+///
+/// Points are yielded in row-major order (i.e. sorted by `y`, then by `x`).
+/// Points that would not be representable by the coordinate type are skipped,
+/// and no arithmetic can overflow.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{coord::Vec2, shape::Area};
+///
+/// # fn main() {
+/// // Clipped at the bounds of `u8`.
+/// let center = Vec2 { x: 0u8, y: 0 };
+/// let points: Vec<_> = Area::disc(center, 2).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 0, y: 0 },
+///         Vec2 { x: 1, y: 0 },
+///         Vec2 { x: 2, y: 0 },
+///         Vec2 { x: 0, y: 1 },
+///         Vec2 { x: 1, y: 1 },
+///         Vec2 { x: 2, y: 1 },
+///         Vec2 { x: 0, y: 2 },
+///         Vec2 { x: 1, y: 2 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Area<T, D = <T as UnsignedDistance>::Output>
+where
+    T: UnsignedDistance<Output = D>,
+{
+    inner: Raster<T, D>,
+}
+
+impl<T, D> Area<T, D>
+where
+    T: UnsignedOffset<Output = D> + Bounded,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    /// Creates an iterator over the points at most at the given distance from
+    /// the center, using the given metric.
+    pub fn new(center: Vec2<T>, radius: D, metric: Metric) -> Self {
+        Self { inner: Raster::new(center, radius, Norm::Metric(metric), false) }
+    }
+
+    /// Creates an iterator over the points of a filled disc with the given
+    /// radius. A point is in the disc if its euclidean distance from the
+    /// center is less than `radius + 1/2`, that is, if `dx² + dy² <= radius² +
+    /// radius`.
+    pub fn disc(center: Vec2<T>, radius: D) -> Self {
+        Self { inner: Raster::new(center, radius, Norm::Euclid, false) }
+    }
+
+    /// Restricts the iterator to the points inside of the given rectangle.
+    pub fn clip_to(mut self, rect: &Rect<T>) -> Self
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero + Ord + Clone,
+    {
+        self.inner.clip_to(rect);
+        self
+    }
+}
+
+impl<T, D> Iterator for Area<T, D>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Iterator over the points around a center, ring by ring, in nondecreasing
+/// distance from the center using a given metric. See [`Vec2::rings`].
+///
+/// Each ring is yielded as in [`Ring::new`], i.e. in row-major order. By
+/// default, rings are yielded until every representable point is visited.
+/// With the Manhattan metric, points can be up to twice `D::max_value()` away
+/// from the center, and the rings keep growing past `D::max_value()` to reach
+/// them, unless limited by [`Rings::max_radius`].
+///
+/// # Examples
+/// ```rust
+/// use gardiz::{coord::Vec2, shape::Metric};
+///
+/// # fn main() {
+/// let center = Vec2 { x: 0u8, y: 1 };
+/// let points: Vec<_> =
+///     center.rings(Metric::Chebyshev).max_radius(1).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 0, y: 1 },
+///         Vec2 { x: 0, y: 0 },
+///         Vec2 { x: 1, y: 0 },
+///         Vec2 { x: 1, y: 1 },
+///         Vec2 { x: 0, y: 2 },
+///         Vec2 { x: 1, y: 2 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rings<T, D = <T as UnsignedDistance>::Output>
+where
+    T: UnsignedDistance<Output = D>,
+{
+    center: Vec2<T>,
+    metric: Metric,
+    bounds: Option<Bounds<T>>,
+    max_radius: Option<D>,
+    radius: Option<Radius<D>>,
+    ring: Option<Ring<T, D>>,
+}
+
+impl<T, D> Rings<T, D>
+where
+    T: UnsignedOffset<Output = D> + Bounded,
+{
+    /// Creates an iterator over the rings around the given center, using the
+    /// given metric.
+    pub fn new(center: Vec2<T>, metric: Metric) -> Self {
+        Self {
+            center,
+            metric,
+            bounds: Some(Bounds::full()),
+            max_radius: None,
+            radius: None,
+            ring: None,
+        }
+    }
+
+    /// Stops the iterator after the ring of the given radius.
+    pub fn max_radius(mut self, radius: D) -> Self {
+        self.max_radius = Some(radius);
+        self
+    }
+
+    /// Restricts the iterator to the points inside of the given rectangle.
+    /// Rings entirely outside of the rectangle are skipped.
+    pub fn clip_to(mut self, rect: &Rect<T>) -> Self
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero + Ord + Clone,
+        D: Unsigned + Roots + Ord + Clone,
+    {
+        if let Some(bounds) = &mut self.bounds {
+            if !bounds.clip_to(rect) {
+                self.bounds = None;
+            }
+        }
+        if let Some(ring) = &mut self.ring {
+            ring.inner.clip_to(rect);
+        }
+        self
+    }
+}
+
+impl<T, D> Iterator for Rings<T, D>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + CheckedAdd + Bounded + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.ring.as_mut().and_then(Ring::next) {
+                break Some(point);
+            }
+            let bounds = self.bounds.as_ref()?;
+            let radius = next_radius(
+                &self.center,
+                bounds,
+                self.metric,
+                self.max_radius.as_ref(),
+                &mut self.radius,
+            );
+            let radius = match radius {
+                Some(radius) => radius,
+                None => {
+                    self.bounds = None;
+                    break None;
+                },
+            };
+            let mut ring =
+                Ring::new(self.center.clone(), radius.base, self.metric);
+            ring.inner.bounds = bounds.clone();
+            ring.inner.excess = radius.excess;
+            self.ring = Some(ring);
+        }
+    }
+}
+
+/// Iterator over the points around a center in a square spiral, in
+/// nondecreasing Chebyshev distance from the center. See [`Vec2::spiral`].
+///
+/// Each ring (a square) is walked clockwise, starting from its top-left
+/// corner. Points that are not representable are skipped.
+///
+/// # Examples
+/// ```rust
+/// use gardiz::coord::Vec2;
+///
+/// # fn main() {
+/// let center = Vec2 { x: 5i32, y: 5 };
+/// let points: Vec<_> = center.spiral(1).collect();
+/// assert_eq!(
+///     points,
+///     vec![
+///         Vec2 { x: 5, y: 5 },
+///         Vec2 { x: 4, y: 4 },
+///         Vec2 { x: 5, y: 4 },
+///         Vec2 { x: 6, y: 4 },
+///         Vec2 { x: 6, y: 5 },
+///         Vec2 { x: 6, y: 6 },
+///         Vec2 { x: 5, y: 6 },
+///         Vec2 { x: 4, y: 6 },
+///         Vec2 { x: 4, y: 5 },
+///     ]
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spiral<T, D = <T as UnsignedDistance>::Output>
+where
+    T: UnsignedDistance<Output = D>,
+{
+    center: Vec2<T>,
+    bounds: Option<Bounds<T>>,
+    max_radius: Option<D>,
+    radius: Option<Radius<D>>,
+    side: usize,
+    walk: Option<Walk<T>>,
+}
+
+impl<T, D> Spiral<T, D>
+where
+    T: UnsignedOffset<Output = D> + Bounded,
+{
+    /// Creates a spiral around the given center, up to the given (Chebyshev)
+    /// radius, inclusive.
+    pub fn new(center: Vec2<T>, max_radius: D) -> Self {
+        Self {
+            center,
+            bounds: Some(Bounds::full()),
+            max_radius: Some(max_radius),
+            radius: None,
+            side: 4,
+            walk: None,
+        }
+    }
+
+    /// Restricts the iterator to the points inside of the given rectangle.
+    /// Rings entirely outside of the rectangle are skipped.
+    pub fn clip_to(mut self, rect: &Rect<T>) -> Self
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero + Ord + Clone,
+    {
+        if let Some(bounds) = &mut self.bounds {
+            if !bounds.clip_to(rect) {
+                self.bounds = None;
+            }
+        }
+        if let (Some(walk), Some(bounds)) = (&mut self.walk, &self.bounds) {
+            walk.clip_to(bounds);
+        }
+        self
+    }
+
+    /// Creates the walk over the current side of the current ring.
+    fn make_walk(&self, bounds: &Bounds<T>, radius: &D) -> Option<Walk<T>>
+    where
+        T: Ord + Clone,
+        D: Unsigned + Clone,
+    {
+        let Vec2 { x, y } = &self.center;
+        let inner = radius.clone() - D::one();
+        let (fixed, start, end, direction) = match self.side {
+            0 => (
+                sub_offset(y, radius),
+                sub_offset(x, radius),
+                add_offset(x, &inner),
+                Direction::Right,
+            ),
+            1 => (
+                add_offset(x, radius),
+                sub_offset(y, radius),
+                add_offset(y, &inner),
+                Direction::Down,
+            ),
+            2 => (
+                add_offset(y, radius),
+                sub_offset(x, &inner),
+                add_offset(x, radius),
+                Direction::Left,
+            ),
+            _ => (
+                sub_offset(x, radius),
+                sub_offset(y, &inner),
+                add_offset(y, radius),
+                Direction::Up,
+            ),
+        };
+        let axis = direction.axis();
+        let fixed = match fixed {
+            Extended::Value(fixed)
+                if fixed >= bounds.min[!axis] && fixed <= bounds.max[!axis] =>
+            {
+                fixed
+            },
+            _ => return None,
+        };
+        let (start, end) = bounds.clip(axis, start, end)?;
+        let (current, end) = match direction {
+            Direction::Right | Direction::Down => (start, end),
+            Direction::Left | Direction::Up => (end, start),
+        };
+        Some(Walk { direction, fixed, current: Some(current), end })
+    }
+}
+
+impl<T, D> Iterator for Spiral<T, D>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + Sub<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + CheckedAdd + Bounded + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.walk.as_mut().and_then(Walk::next) {
+                break Some(point);
+            }
+            let bounds = self.bounds.clone()?;
+            if self.side < 4 {
+                let radius = self.radius.clone()?;
+                self.walk = self.make_walk(&bounds, &radius.base);
+                self.side += 1;
+                continue;
+            }
+            let radius = next_radius(
+                &self.center,
+                &bounds,
+                Metric::Chebyshev,
+                self.max_radius.as_ref(),
+                &mut self.radius,
+            );
+            match radius {
+                Some(radius) if radius.base.is_zero() => {
+                    if bounds.contains(&self.center) {
+                        break Some(self.center.clone());
+                    }
+                },
+                Some(_) => self.side = 0,
+                None => {
+                    self.bounds = None;
+                    break None;
+                },
+            }
+        }
+    }
+}
+
+/// Walk over one side of a spiral's ring.
+#[derive(Debug, Clone)]
+struct Walk<T> {
+    direction: Direction,
+    fixed: T,
+    current: Option<T>,
+    end: T,
+}
+
+impl<T> Walk<T>
+where
+    T: Ord + Clone,
+{
+    fn clip_to(&mut self, bounds: &Bounds<T>) {
+        let axis = self.direction.axis();
+        let fixed_in_bounds =
+            self.fixed >= bounds.min[!axis] && self.fixed <= bounds.max[!axis];
+        let current = self.current.take().filter(|_| fixed_in_bounds);
+        self.current = current.and_then(|current| {
+            let (start, end) = match self.direction {
+                Direction::Right | Direction::Down => {
+                    (current, self.end.clone())
+                },
+                Direction::Left | Direction::Up => (self.end.clone(), current),
+            };
+            let (start, end) = bounds.clip(
+                axis,
+                Extended::Value(start),
+                Extended::Value(end),
+            )?;
+            match self.direction {
+                Direction::Right | Direction::Down => {
+                    self.end = end;
+                    Some(start)
+                },
+                Direction::Left | Direction::Up => {
+                    self.end = start;
+                    Some(end)
+                },
+            }
+        });
+    }
+}
+
+impl<T> Iterator for Walk<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + One + Ord + Clone,
+{
+    type Item = Vec2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current.take()?;
+        if current != self.end {
+            self.current = Some(match self.direction {
+                Direction::Right | Direction::Down => {
+                    current.clone() + T::one()
+                },
+                Direction::Left | Direction::Up => current.clone() - T::one(),
+            });
+        }
+        let point = match self.direction.axis() {
+            Axis::X => Vec2 { x: current, y: self.fixed.clone() },
+            Axis::Y => Vec2 { x: self.fixed.clone(), y: current },
+        };
+        Some(point)
+    }
+}
+
+/// Advances the radius of a ring-by-ring iterator, returning `None` when there
+/// are no rings left to visit. The first radius is the one of the nearest
+/// ring which can have points in bounds.
+fn next_radius<T, D>(
+    center: &Vec2<T>,
+    bounds: &Bounds<T>,
+    metric: Metric,
+    max_radius: Option<&D>,
+    radius: &mut Option<Radius<D>>,
+) -> Option<Radius<D>>
+where
+    T: UnsignedDistance<Output = D> + Ord + Clone,
+    D: Unsigned + CheckedAdd + Bounded + Ord + Clone,
+{
+    let (near, far) = bounds.radii(center, metric);
+    let last = match max_radius {
+        Some(max_radius) => far.min(Radius::new(max_radius.clone())),
+        None => far,
+    };
+    let next = match radius.take() {
+        None => near,
+        Some(current) if current < last => near.max(current.successor()),
+        Some(_) => return None,
+    };
+    if next > last {
+        return None;
+    }
+    *radius = Some(next.clone());
+    Some(next)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Norm {
+    Metric(Metric),
+    Euclid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Half {
+    Top,
+    Bottom,
+}
+
+/// Shared implementation of shape iterators. Rows are visited from the top to
+/// the bottom, where `row` is the distance from the center's row, thus going
+/// from `radius` to `0` and then back to `radius`. Each row is made of at most
+/// two spans of points, symmetric around the center's column.
+#[derive(Debug, Clone)]
+struct Raster<T, D> {
+    center: Vec2<T>,
+    bounds: Bounds<T>,
+    radius: D,
+    /// How much the radius of a Manhattan outline exceeds `radius`, which is
+    /// then `D::max_value()`. Rows nearer than the excess have no points.
+    excess: D,
+    norm: Norm,
+    outline: bool,
+    next_row: Option<(Half, D)>,
+    disc: DiscRow<D>,
+    y: Option<T>,
+    spans: [Option<(T, T)>; 2],
+}
+
+impl<T, D> Raster<T, D>
+where
+    T: UnsignedOffset<Output = D> + Bounded,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    fn new(center: Vec2<T>, radius: D, norm: Norm, outline: bool) -> Self {
+        let next_row = if radius.is_zero() {
+            (Half::Bottom, D::zero())
+        } else {
+            (Half::Top, radius.clone())
+        };
+        Self {
+            center,
+            bounds: Bounds::full(),
+            disc: DiscRow::at_radius(radius.clone()),
+            radius,
+            excess: D::zero(),
+            norm,
+            outline,
+            next_row: Some(next_row),
+            y: None,
+            spans: [None, None],
+        }
+    }
+
+    fn clip_to(&mut self, rect: &Rect<T>)
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero + Ord + Clone,
+    {
+        if !self.bounds.clip_to(rect) {
+            self.next_row = None;
+        }
+        let y_in_bounds = match &self.y {
+            Some(y) => *y >= self.bounds.min.y && *y <= self.bounds.max.y,
+            None => false,
+        };
+        let bounds = &self.bounds;
+        for span in &mut self.spans {
+            *span =
+                span.take().filter(|_| y_in_bounds).and_then(|(start, end)| {
+                    bounds.clip(
+                        Axis::X,
+                        Extended::Value(start),
+                        Extended::Value(end),
+                    )
+                });
+        }
+    }
+}
+
+impl<T, D> Raster<T, D>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    fn next(&mut self) -> Option<Vec2<T>> {
+        loop {
+            if let Some((x, end)) = &mut self.spans[0] {
+                let point = Vec2 { x: x.clone(), y: self.y.clone()? };
+                if x == end {
+                    self.spans[0] = self.spans[1].take();
+                } else {
+                    *x = x.clone() + T::one();
+                }
+                break Some(point);
+            } else if self.spans[1].is_some() {
+                self.spans[0] = self.spans[1].take();
+            } else {
+                self.load_row()?;
+            }
+        }
+    }
+
+    /// Loads the spans of the next row, returning `None` if there are no more
+    /// rows.
+    fn load_row(&mut self) -> Option<()> {
+        let (half, row) = self.skip_rows()?;
+        let (min, max) = (&self.bounds.min.y, &self.bounds.max.y);
+        self.y = match half {
+            Half::Top => match sub_offset(&self.center.y, &row) {
+                Extended::Value(y) if y >= *min && y <= *max => Some(y),
+                _ => None,
+            },
+            Half::Bottom => match add_offset(&self.center.y, &row) {
+                Extended::Value(y) if y <= *max => Some(y).filter(|y| y >= min),
+                // Every following row would be out of bounds too.
+                _ => return None,
+            },
+        };
+
+        self.spans = [None, None];
+        if self.y.is_some() {
+            if let Some((low, high)) = self.row_offsets(&row) {
+                self.load_spans(&low, &high);
+            }
+        }
+
+        self.next_row = match half {
+            Half::Top => {
+                let previous = row.clone() - D::one();
+                self.disc.step_back(&row);
+                if previous.is_zero() {
+                    Some((Half::Bottom, previous))
+                } else {
+                    Some((Half::Top, previous))
+                }
+            },
+            Half::Bottom if row < self.radius => {
+                self.disc.step_forward(&row);
+                Some((Half::Bottom, row + D::one()))
+            },
+            Half::Bottom => None,
+        };
+        Some(())
+    }
+
+    /// Takes the next row, jumping straight to the first row inside of the
+    /// bounds if the next row is above them, and over the rows nearer than the
+    /// excess. Returns `None` if no row left is inside of the bounds.
+    fn skip_rows(&mut self) -> Option<(Half, D)> {
+        let (half, row) = self.skip_rows_above()?;
+        if row >= self.excess {
+            return Some((half, row));
+        }
+        let first = self.excess.clone();
+        if first > self.radius {
+            return None;
+        }
+        self.disc = DiscRow::at_row(&self.radius, &first);
+        Some((Half::Bottom, first))
+    }
+
+    /// Takes the next row, jumping straight to the first row inside of the
+    /// bounds if the next row is above them. Returns `None` if no row left is
+    /// inside of the bounds.
+    fn skip_rows_above(&mut self) -> Option<(Half, D)> {
+        let (half, row) = self.next_row.take()?;
+        let (center, min) = (&self.center.y, &self.bounds.min.y);
+        let above = match half {
+            Half::Top => match sub_offset(center, &row) {
+                Extended::Value(y) if y > self.bounds.max.y => return None,
+                Extended::Value(y) => y < *min,
+                _ => true,
+            },
+            Half::Bottom => match add_offset(center, &row) {
+                Extended::Value(y) => y < *min,
+                _ => false,
+            },
+        };
+        if !above {
+            return Some((half, row));
+        }
+        let first = if center < min {
+            (Half::Bottom, min.unsigned_distance(center))
+        } else {
+            let distance = center.unsigned_distance(min);
+            if distance.is_zero() {
+                (Half::Bottom, distance)
+            } else {
+                (Half::Top, distance)
+            }
+        };
+        if first.1 > self.radius {
+            return None;
+        }
+        self.disc = DiscRow::at_row(&self.radius, &first.1);
+        Some(first)
+    }
+
+    /// Computes the range of absolute offsets from the center's column that
+    /// are part of the shape, at the given distance from the center's row.
+    fn row_offsets(&self, row: &D) -> Option<(D, D)> {
+        let radius = &self.radius;
+        let (low, high) = match (self.norm, self.outline) {
+            (Norm::Metric(Metric::Manhattan), false) => {
+                (D::zero(), radius.clone() - row.clone())
+            },
+            (Norm::Metric(Metric::Manhattan), true) => {
+                if *row < self.excess {
+                    return None;
+                }
+                let offset =
+                    radius.clone() - (row.clone() - self.excess.clone());
+                (offset.clone(), offset)
+            },
+            (Norm::Metric(Metric::Chebyshev), false) => {
+                (D::zero(), radius.clone())
+            },
+            (Norm::Metric(Metric::Chebyshev), true) => {
+                if row == radius {
+                    (D::zero(), radius.clone())
+                } else {
+                    (radius.clone(), radius.clone())
+                }
+            },
+            (Norm::Euclid, false) => (D::zero(), self.disc.width.clone()),
+            (Norm::Euclid, true) => {
+                let width = self.disc.width.clone();
+                if *row < width {
+                    (width.clone(), width)
+                } else if row == radius {
+                    (D::zero(), width)
+                } else {
+                    // Points of the outline in this row are the ones not
+                    // covered by the next row (in the direction away from
+                    // the center).
+                    let mut next = self.disc.clone();
+                    next.step_forward(row);
+                    (next.width + D::one(), width)
+                }
+            },
+        };
+        if low <= high {
+            Some((low, high))
+        } else {
+            None
+        }
+    }
+
+    /// Loads the spans of the current row given the range of absolute offsets
+    /// from the center's column, clipping them to the bounds.
+    fn load_spans(&mut self, low: &D, high: &D) {
+        let center = &self.center.x;
+        if !high.is_zero() {
+            let low = if low.is_zero() { D::one() } else { low.clone() };
+            self.spans[0] = self.bounds.clip(
+                Axis::X,
+                sub_offset(center, high),
+                sub_offset(center, &low),
+            );
+        }
+        self.spans[1] = self.bounds.clip(
+            Axis::X,
+            add_offset(center, low),
+            add_offset(center, high),
+        );
+    }
+}
+
+/// A coordinate computed from a center and an offset, which might not be
+/// representable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Extended<T> {
+    Below,
+    Value(T),
+    Above,
+}
+
+fn add_offset<T>(center: &T, offset: &T::Output) -> Extended<T>
+where
+    T: UnsignedOffset,
+{
+    center.checked_add_offset(offset).map_or(Extended::Above, Extended::Value)
+}
+
+fn sub_offset<T>(center: &T, offset: &T::Output) -> Extended<T>
+where
+    T: UnsignedOffset,
+{
+    center.checked_sub_offset(offset).map_or(Extended::Below, Extended::Value)
+}
+
+/// A radius of a ring-by-ring iterator. Manhattan distances between
+/// representable points can be up to twice `D::max_value()`, so the radius is
+/// `base`, plus `excess` when `base` is `D::max_value()`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Radius<D> {
+    base: D,
+    excess: D,
+}
+
+impl<D> Radius<D>
+where
+    D: Unsigned + CheckedAdd + Bounded + Ord + Clone,
+{
+    fn new(base: D) -> Self {
+        Self { base, excess: D::zero() }
+    }
+
+    /// The radius `first + second`.
+    fn sum(first: D, second: D) -> Self {
+        match first.checked_add(&second) {
+            Some(base) => Self::new(base),
+            None => {
+                let base = D::max_value();
+                let excess = first - (base.clone() - second);
+                Self { base, excess }
+            },
+        }
+    }
+
+    /// The next radius, which must be representable.
+    fn successor(self) -> Self {
+        if self.base < D::max_value() {
+            Self::new(self.base + D::one())
+        } else {
+            Self { base: self.base, excess: self.excess + D::one() }
+        }
+    }
+}
+
+/// Inclusive bounds of the points yielded by a shape iterator.
+#[derive(Debug, Clone)]
+struct Bounds<T> {
+    min: Vec2<T>,
+    max: Vec2<T>,
+}
+
+impl<T> Bounds<T>
+where
+    T: Bounded,
+{
+    fn full() -> Self {
+        Self {
+            min: Vec2 { x: T::min_value(), y: T::min_value() },
+            max: Vec2 { x: T::max_value(), y: T::max_value() },
+        }
+    }
+}
+
+impl<T> Bounds<T>
+where
+    T: Ord + Clone,
+{
+    /// Intersects these bounds with the given rectangle, returning whether
+    /// the bounds are still not empty.
+    fn clip_to(&mut self, rect: &Rect<T>) -> bool
+    where
+        T: SaturatingAdd + SaturatingSub + One + Zero,
+    {
+        if rect.is_empty() {
+            return false;
+        }
+        let end = rect.saturating_end_incl();
+        self.min = self.min.clone().elemwise_max(rect.start.clone());
+        self.max = self.max.clone().elemwise_min(end);
+        self.min.x <= self.max.x && self.min.y <= self.max.y
+    }
+
+    fn contains(&self, point: &Vec2<T>) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Clips the given inclusive range on the given axis to these bounds.
+    fn clip(
+        &self,
+        axis: Axis,
+        start: Extended<T>,
+        end: Extended<T>,
+    ) -> Option<(T, T)> {
+        let (min, max) = (&self.min[axis], &self.max[axis]);
+        let start = match start {
+            Extended::Below => min.clone(),
+            Extended::Value(start) => start.max(min.clone()),
+            Extended::Above => return None,
+        };
+        let end = match end {
+            Extended::Below => return None,
+            Extended::Value(end) => end.min(max.clone()),
+            Extended::Above => max.clone(),
+        };
+        if start <= end {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Range of radii of rings (using the given metric) around the given
+    /// center that can have points inside of these bounds.
+    fn radii<D>(
+        &self,
+        center: &Vec2<T>,
+        metric: Metric,
+    ) -> (Radius<D>, Radius<D>)
+    where
+        T: UnsignedDistance<Output = D>,
+        D: Unsigned + CheckedAdd + Bounded + Ord + Clone,
+    {
+        let near = Vec2::from_axes(|axis| {
+            let coord = &center[axis];
+            if *coord < self.min[axis] {
+                coord.unsigned_distance(&self.min[axis])
+            } else if *coord > self.max[axis] {
+                coord.unsigned_distance(&self.max[axis])
+            } else {
+                D::zero()
+            }
+        });
+        let far = Vec2::from_axes(|axis| {
+            let coord = &center[axis];
+            let to_min = coord.unsigned_distance(&self.min[axis]);
+            let to_max = coord.unsigned_distance(&self.max[axis]);
+            to_min.max(to_max)
+        });
+        match metric {
+            Metric::Manhattan => {
+                (Radius::sum(near.x, near.y), Radius::sum(far.x, far.y))
+            },
+            Metric::Chebyshev => {
+                (Radius::new(near.x.max(near.y)), Radius::new(far.x.max(far.y)))
+            },
+        }
+    }
+}
+
+/// Half-width of a disc at some row, i.e. the greatest `width` such that
+/// `width² + row² <= radius² + radius`. The `slack` is how far the inequality
+/// is from not holding, i.e. `radius² + radius - row² - width²`, which is
+/// always less than `2 * width + 1`.
+#[derive(Debug, Clone)]
+struct DiscRow<D> {
+    width: D,
+    slack: Doubled<D>,
+}
+
+impl<D> DiscRow<D>
+where
+    D: Unsigned + Roots + Ord + Clone,
+{
+    /// Half-width at the row `radius`, where `width² <= radius`.
+    fn at_radius(radius: D) -> Self {
+        let width = radius.sqrt();
+        let slack = radius - width.clone() * width.clone();
+        Self { width, slack: Doubled::new(slack) }
+    }
+
+    /// Half-width at the given row, which must not be greater than the
+    /// radius. Takes time proportional to the number of bits of `D`.
+    fn at_row(radius: &D, row: &D) -> Self {
+        let wide = Wide::new(radius);
+        let squares = wide.add(&wide.square(radius), &wide.value(radius));
+        // radius² + radius - row², i.e. the greatest possible width².
+        let limit = wide.sub(&squares, &wide.square(row));
+        let two = D::one() + D::one();
+        let mut width = D::zero();
+        let mut high = radius.clone();
+        while width < high {
+            let middle =
+                high.clone() - (high.clone() - width.clone()) / two.clone();
+            if wide.square(&middle) <= limit {
+                width = middle;
+            } else {
+                high = middle - D::one();
+            }
+        }
+        let slack = wide.sub(&limit, &wide.square(&width));
+        Self { slack: wide.doubled(&slack), width }
+    }
+
+    /// Moves from the given row to the next row (away from the center). The
+    /// next row must not be greater than the radius.
+    fn step_forward(&mut self, row: &D) {
+        // row² grows by 2 * row + 1.
+        let growth = Doubled::odd(row.clone());
+        if self.slack >= growth {
+            self.slack = self.slack.clone().sub(growth);
+        } else {
+            let mut deficit = growth.sub(self.slack.clone());
+            loop {
+                // width² shrinks by 2 * width - 1.
+                let shrink = Doubled::odd(self.width.clone() - D::one());
+                self.width = self.width.clone() - D::one();
+                if deficit <= shrink {
+                    self.slack = shrink.sub(deficit);
+                    break;
+                }
+                deficit = deficit.sub(shrink);
+            }
+        }
+    }
+
+    /// Moves from the given row to the previous row (towards the center). The
+    /// given row must not be zero.
+    fn step_back(&mut self, row: &D) {
+        // row² shrinks by 2 * row - 1.
+        let mut surplus = Doubled::odd(row.clone() - D::one());
+        loop {
+            // width² would grow by 2 * width + 1.
+            let room = Doubled::odd(self.width.clone()).sub(self.slack.clone());
+            if surplus >= room {
+                surplus = surplus.sub(room);
+                self.slack = Doubled::new(D::zero());
+                self.width = self.width.clone() + D::one();
+            } else {
+                self.slack = self.slack.clone().add(surplus);
+                break;
+            }
+        }
+    }
+}
+
+/// The number `2 * half + (odd as D)`, used so that doubled quantities do not
+/// overflow.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Doubled<D> {
+    half: D,
+    odd: bool,
+}
+
+impl<D> Doubled<D>
+where
+    D: Unsigned + Ord + Clone,
+{
+    fn new(value: D) -> Self {
+        let half = value.clone() / (D::one() + D::one());
+        let odd = half.clone() + half.clone() != value;
+        Self { half, odd }
+    }
+
+    /// The number `2 * half + 1`.
+    fn odd(half: D) -> Self {
+        Self { half, odd: true }
+    }
+
+    /// Adds two numbers. The sum must be representable.
+    fn add(self, other: Self) -> Self {
+        let mut half = self.half + other.half;
+        if self.odd && other.odd {
+            half = half + D::one();
+        }
+        Self { half, odd: self.odd != other.odd }
+    }
+
+    /// Subtracts two numbers. The minuend must not be less than the
+    /// subtrahend.
+    fn sub(self, other: Self) -> Self {
+        let mut half = self.half - other.half;
+        if other.odd && !self.odd {
+            half = half - D::one();
+        }
+        Self { half, odd: self.odd != other.odd }
+    }
+}
+
+/// Arithmetic on numbers written with four digits, least significant first,
+/// in base `sqrt(radius) + 1`, used to compute squares of radii without
+/// overflow. Numbers up to the radius have two digits, and the product of two
+/// digits (plus two carries) fits in `D`.
+#[derive(Debug, Clone)]
+struct Wide<D> {
+    base: D,
+}
+
+impl<D> Wide<D>
+where
+    D: Unsigned + Roots + Ord + Clone,
+{
+    const DIGITS: usize = 4;
+
+    fn new(radius: &D) -> Self {
+        Self { base: radius.sqrt() + D::one() }
+    }
+
+    /// Writes a number not greater than the radius.
+    fn value(&self, number: &D) -> WideNumber<D> {
+        let mut digits = vec![D::zero(); Self::DIGITS];
+        digits[0] = number.clone() % self.base.clone();
+        digits[1] = number.clone() / self.base.clone();
+        WideNumber { digits }
+    }
+
+    /// Squares a number not greater than the radius.
+    fn square(&self, number: &D) -> WideNumber<D> {
+        let factor = self.value(number).digits;
+        let mut digits = vec![D::zero(); Self::DIGITS];
+        for i in 0 .. 2 {
+            let mut carry = D::zero();
+            for j in 0 .. 2 {
+                let total = factor[i].clone() * factor[j].clone()
+                    + digits[i + j].clone()
+                    + carry;
+                digits[i + j] = total.clone() % self.base.clone();
+                carry = total / self.base.clone();
+            }
+            digits[i + 2] = carry;
+        }
+        WideNumber { digits }
+    }
+
+    fn add(
+        &self,
+        left: &WideNumber<D>,
+        right: &WideNumber<D>,
+    ) -> WideNumber<D> {
+        let mut carry = D::zero();
+        let digits = left
+            .digits
+            .iter()
+            .zip(&right.digits)
+            .map(|(left, right)| {
+                let total = left.clone() + right.clone() + carry.clone();
+                carry = total.clone() / self.base.clone();
+                total % self.base.clone()
+            })
+            .collect();
+        WideNumber { digits }
+    }
+
+    /// Subtracts two numbers. The minuend must not be less than the
+    /// subtrahend.
+    fn sub(
+        &self,
+        left: &WideNumber<D>,
+        right: &WideNumber<D>,
+    ) -> WideNumber<D> {
+        let mut borrow = D::zero();
+        let digits = left
+            .digits
+            .iter()
+            .zip(&right.digits)
+            .map(|(left, right)| {
+                let subtrahend = right.clone() + borrow.clone();
+                if *left >= subtrahend {
+                    borrow = D::zero();
+                    left.clone() - subtrahend
+                } else {
+                    borrow = D::one();
+                    left.clone() + self.base.clone() - subtrahend
+                }
+            })
+            .collect();
+        WideNumber { digits }
+    }
+
+    /// Converts a number whose half is representable by `D`.
+    fn doubled(&self, number: &WideNumber<D>) -> Doubled<D> {
+        let two = D::one() + D::one();
+        let mut half = D::zero();
+        let mut remainder = D::zero();
+        for digit in number.digits.iter().rev() {
+            let current = remainder * self.base.clone() + digit.clone();
+            half = half * self.base.clone() + current.clone() / two.clone();
+            remainder = current % two.clone();
+        }
+        Doubled { half, odd: !remainder.is_zero() }
+    }
+}
+
+/// A number computed by [`Wide`], compared from its most significant digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WideNumber<D> {
+    digits: Vec<D>,
+}
+
+impl<D> PartialOrd for WideNumber<D>
+where
+    D: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for WideNumber<D>
+where
+    D: Ord,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.digits.iter().rev().cmp(other.digits.iter().rev())
+    }
+}
@@ -0,0 +1,382 @@
+use super::{Area, DiscRow, Metric, Ring};
+use crate::{bits::UnsignedOffset, coord::Vec2, rect::Rect};
+use num::{integer::Roots, Bounded, One, Unsigned};
+use std::{collections::HashSet, ops::Add};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Ring(Metric),
+    Area(Metric),
+    Circle,
+    Disc,
+}
+
+/// Brute force offsets of the given shape, in row-major order.
+fn reference(radius: i64, kind: Kind) -> Vec<Vec2<i64>> {
+    let in_disc =
+        |dx: i64, dy: i64| dx * dx + dy * dy <= radius * radius + radius;
+    // Points of the disc with a horizontal or vertical neighbour outside.
+    let on_circle = |dx: i64, dy: i64| {
+        in_disc(dx, dy)
+            && !(in_disc(dx - 1, dy)
+                && in_disc(dx + 1, dy)
+                && in_disc(dx, dy - 1)
+                && in_disc(dx, dy + 1))
+    };
+    let mut offsets = Vec::new();
+    for dy in -radius ..= radius {
+        for dx in -radius ..= radius {
+            let manhattan = dx.abs() + dy.abs();
+            let chebyshev = dx.abs().max(dy.abs());
+            let included = match kind {
+                Kind::Ring(Metric::Manhattan) => manhattan == radius,
+                Kind::Ring(Metric::Chebyshev) => chebyshev == radius,
+                Kind::Area(Metric::Manhattan) => manhattan <= radius,
+                Kind::Area(Metric::Chebyshev) => chebyshev <= radius,
+                Kind::Circle => on_circle(dx, dy),
+                Kind::Disc => in_disc(dx, dy),
+            };
+            if included {
+                offsets.push(Vec2 { x: dx, y: dy });
+            }
+        }
+    }
+    offsets
+}
+
+fn collect<T, D>(center: Vec2<T>, radius: D, kind: Kind) -> Vec<Vec2<T>>
+where
+    T: UnsignedOffset<Output = D>
+        + Bounded
+        + Add<Output = T>
+        + One
+        + Ord
+        + Clone,
+    D: Unsigned + Roots + Ord + Clone,
+{
+    match kind {
+        Kind::Ring(metric) => Ring::new(center, radius, metric).collect(),
+        Kind::Area(metric) => Area::new(center, radius, metric).collect(),
+        Kind::Circle => Ring::circle(center, radius).collect(),
+        Kind::Disc => Area::disc(center, radius).collect(),
+    }
+}
+
+const KINDS: [Kind; 6] = [
+    Kind::Ring(Metric::Manhattan),
+    Kind::Ring(Metric::Chebyshev),
+    Kind::Area(Metric::Manhattan),
+    Kind::Area(Metric::Chebyshev),
+    Kind::Circle,
+    Kind::Disc,
+];
+
+#[test]
+fn matches_reference() {
+    let center = Vec2 { x: -3i32, y: 7 };
+    for &kind in &KINDS {
+        for radius in 0 .. 12 {
+            let expected: Vec<_> = reference(i64::from(radius), kind)
+                .into_iter()
+                .map(|offset| Vec2 {
+                    x: center.x + offset.x as i32,
+                    y: center.y + offset.y as i32,
+                })
+                .collect();
+            let points = collect(center, radius, kind);
+            assert_eq!(points, expected, "{:?} of radius {}", kind, radius);
+        }
+    }
+}
+
+#[test]
+fn circle_of_radius_one() {
+    let center = Vec2 { x: 0i32, y: 0 };
+    let points: Vec<_> = Ring::circle(center, 1u32).collect();
+    assert_eq!(points.len(), 8);
+    assert!(!points.contains(&center));
+}
+
+#[test]
+fn circle_is_connected_border_of_disc() {
+    let center = Vec2 { x: 0i64, y: 0 };
+    for radius in 1 .. 40 {
+        let circle: Vec<_> = Ring::circle(center, radius).collect();
+        let disc: Vec<_> = Area::disc(center, radius).collect();
+        for point in &circle {
+            assert!(disc.contains(point));
+            let neighbours = circle
+                .iter()
+                .filter(|other| point.chebyshev_dist(**other) == 1)
+                .count();
+            assert!(neighbours >= 2, "radius {} at {}", radius, point);
+        }
+    }
+}
+
+#[test]
+fn clipped_at_bounds() {
+    let cases = [
+        (Vec2 { x: 0u8, y: 0 }, 255u8),
+        (Vec2 { x: 255, y: 255 }, 255),
+        (Vec2 { x: 250, y: 3 }, 200),
+        (Vec2 { x: 128, y: 127 }, 130),
+    ];
+    for &(center, radius) in &cases {
+        for &kind in &KINDS {
+            let expected: Vec<_> = reference(i64::from(radius), kind)
+                .into_iter()
+                .map(|offset| {
+                    offset.zip_with(center, |offset, center| {
+                        offset + i64::from(center)
+                    })
+                })
+                .filter(|point| {
+                    (0 ..= 255).contains(&point.x)
+                        && (0 ..= 255).contains(&point.y)
+                })
+                .map(|point| point.map(|coord| coord as u8))
+                .collect();
+            let points = collect(center, radius, kind);
+            assert_eq!(
+                points, expected,
+                "{:?} of radius {} at {}",
+                kind, radius, center
+            );
+        }
+    }
+}
+
+#[test]
+fn disc_row_at_any_row() {
+    for radius in 0 ..= u8::MAX {
+        let mut stepped = DiscRow::at_radius(radius);
+        for row in (0 ..= radius).rev() {
+            let jumped = DiscRow::at_row(&radius, &row);
+            assert_eq!(jumped.width, stepped.width, "{} at {}", radius, row);
+            assert_eq!(jumped.slack, stepped.slack, "{} at {}", radius, row);
+            if row > 0 {
+                stepped.step_back(&row);
+            }
+        }
+    }
+
+    let radius = u64::MAX - 7;
+    for &row in &[0, 1, 1 << 40, radius / 2, radius - 1, radius] {
+        let disc = DiscRow::at_row(&radius, &row);
+        let (radius, row) = (u128::from(radius), u128::from(row));
+        let width = u128::from(disc.width);
+        let slack =
+            2 * u128::from(disc.slack.half) + u128::from(disc.slack.odd as u8);
+        // radius² + radius - row² - width², without overflowing u128.
+        let limit = (radius - row) * (radius + row) + radius;
+        assert_eq!(limit - width * width, slack);
+        assert!(slack < 2 * width + 1);
+    }
+}
+
+#[test]
+fn huge_radius_skips_rows() {
+    let radius = 1u32 << 28;
+    let corner = Vec2 { x: 0u32, y: 0 };
+    assert_eq!(Area::disc(corner, radius).next(), Some(corner));
+    assert_eq!(
+        Ring::circle(corner, radius).next(),
+        Some(Vec2 { x: radius, y: 0 })
+    );
+
+    let center = Vec2 { x: 1u32 << 30, y: 1 << 30 };
+    let rect = Rect {
+        start: Vec2 { x: center.x + 5, y: center.y + 7 },
+        size: Vec2 { x: 2, y: 2 },
+    };
+    let points: Vec<_> = Area::disc(center, radius).clip_to(&rect).collect();
+    assert_eq!(points, rect.rows().collect::<Vec<_>>());
+
+    let rect = Rect {
+        start: Vec2 { x: center.x - 2, y: center.y + radius - 1 },
+        size: Vec2 { x: 5, y: 2 },
+    };
+    let points: Vec<_> =
+        Ring::new(center, radius, Metric::Manhattan).clip_to(&rect).collect();
+    assert_eq!(
+        points,
+        vec![
+            Vec2 { x: center.x - 1, y: center.y + radius - 1 },
+            Vec2 { x: center.x + 1, y: center.y + radius - 1 },
+            Vec2 { x: center.x, y: center.y + radius },
+        ]
+    );
+
+    let rect = Rect {
+        start: Vec2 { x: center.x, y: center.y - radius },
+        size: Vec2 { x: 1, y: 1 },
+    };
+    let points: Vec<_> = Ring::circle(center, radius).clip_to(&rect).collect();
+    assert_eq!(points, vec![rect.start]);
+}
+
+#[test]
+fn signed_bounds() {
+    let center = Vec2 { x: i8::MIN, y: i8::MAX };
+    let points: Vec<_> = Ring::new(center, 255, Metric::Chebyshev).collect();
+    let top = (-128 ..= 127).map(|x| Vec2 { x, y: -128 });
+    let right = (-127 ..= 127).map(|y| Vec2 { x: 127, y });
+    assert_eq!(points, top.chain(right).collect::<Vec<_>>());
+
+    let points: Vec<_> = Area::new(center, 255, Metric::Manhattan).collect();
+    assert_eq!(points.len(), 256 * 257 / 2);
+    let center = center.map(i16::from);
+    assert!(points
+        .into_iter()
+        .all(|point| { point.map(i16::from).manhattan_dist(center) <= 255 }));
+}
+
+fn distance(metric: Metric, first: Vec2<i64>, second: Vec2<i64>) -> i64 {
+    match metric {
+        Metric::Manhattan => first.manhattan_dist(second),
+        Metric::Chebyshev => first.chebyshev_dist(second),
+    }
+}
+
+#[test]
+fn rings() {
+    let center = Vec2 { x: 4i64, y: -9 };
+    for &metric in &[Metric::Manhattan, Metric::Chebyshev] {
+        let points: Vec<_> = center.rings(metric).max_radius(6).collect();
+        let area: Vec<_> = Area::new(center, 6, metric).collect();
+        assert_eq!(points.len(), area.len());
+        let unique: HashSet<_> = points.iter().collect();
+        assert_eq!(unique, area.iter().collect());
+        for pair in points.windows(2) {
+            assert!(
+                distance(metric, center, pair[0])
+                    <= distance(metric, center, pair[1])
+            );
+        }
+    }
+}
+
+#[test]
+fn rings_exhaust_type() {
+    let center = Vec2 { x: 3u8, y: 250 };
+    let points: HashSet<_> = center.rings(Metric::Chebyshev).collect();
+    assert_eq!(points.len(), 256 * 256);
+    let points: HashSet<_> = center.rings(Metric::Manhattan).collect();
+    assert_eq!(points.len(), 256 * 256);
+
+    // From a corner, about half of the points are farther than `u8::MAX`.
+    let corner = Vec2 { x: 0u8, y: 0 };
+    let points: Vec<_> = corner.rings(Metric::Manhattan).collect();
+    assert_eq!(points.len(), 256 * 256);
+    assert_eq!(points.last(), Some(&Vec2 { x: 255, y: 255 }));
+    let distances: Vec<_> = points
+        .iter()
+        .map(|point| u16::from(point.x) + u16::from(point.y))
+        .collect();
+    assert!(distances.windows(2).all(|pair| pair[0] <= pair[1]));
+    let far = distances.iter().filter(|&&distance| distance > 255).count();
+    assert_eq!(far, 32640);
+
+    let points: Vec<_> =
+        corner.rings(Metric::Manhattan).max_radius(u8::MAX).collect();
+    assert_eq!(points.len(), 32896);
+
+    let signed = Vec2 { x: i8::MAX, y: i8::MIN };
+    let points: HashSet<_> = signed.rings(Metric::Manhattan).collect();
+    assert_eq!(points.len(), 256 * 256);
+
+    let rect =
+        Rect { start: Vec2 { x: 250, y: 240 }, size: Vec2 { x: 6, y: 3 } };
+    let points: Vec<_> =
+        corner.rings(Metric::Manhattan).clip_to(&rect).collect();
+    let mut expected: Vec<_> = rect.rows().collect();
+    expected.sort_by_key(|point| {
+        (u16::from(point.x) + u16::from(point.y), point.y, point.x)
+    });
+    assert_eq!(points, expected);
+}
+
+#[test]
+fn rings_clipped() {
+    let center = Vec2 { x: 0i32, y: 0 };
+    let rect = Rect { start: Vec2 { x: 3, y: -1 }, size: Vec2 { x: 2, y: 3 } };
+    let points: Vec<_> =
+        center.rings(Metric::Manhattan).clip_to(&rect).collect();
+    assert_eq!(
+        points,
+        vec![
+            Vec2 { x: 3, y: 0 },
+            Vec2 { x: 3, y: -1 },
+            Vec2 { x: 4, y: 0 },
+            Vec2 { x: 3, y: 1 },
+            Vec2 { x: 4, y: -1 },
+            Vec2 { x: 4, y: 1 },
+        ]
+    );
+
+    let empty = Rect { start: Vec2 { x: 3, y: -1 }, size: Vec2 { x: 0, y: 3 } };
+    assert_eq!(center.rings(Metric::Chebyshev).clip_to(&empty).next(), None);
+
+    let points: Vec<_> =
+        Ring::new(center, 4, Metric::Chebyshev).clip_to(&rect).collect();
+    assert_eq!(
+        points,
+        vec![Vec2 { x: 4, y: -1 }, Vec2 { x: 4, y: 0 }, Vec2 { x: 4, y: 1 }]
+    );
+    let points: Vec<_> = Area::disc(center, 3).clip_to(&rect).collect();
+    assert_eq!(
+        points,
+        vec![Vec2 { x: 3, y: -1 }, Vec2 { x: 3, y: 0 }, Vec2 { x: 3, y: 1 }]
+    );
+}
+
+#[test]
+fn spiral() {
+    let center = Vec2 { x: -2i64, y: 7 };
+    let points: Vec<_> = center.spiral(5).collect();
+    let area: HashSet<_> = Area::new(center, 5, Metric::Chebyshev).collect();
+    assert_eq!(points.len(), area.len());
+    assert_eq!(points.iter().copied().collect::<HashSet<_>>(), area);
+    assert_eq!(points[0], center);
+    for pair in points[1 ..].windows(2) {
+        let first = pair[0].chebyshev_dist(center);
+        let second = pair[1].chebyshev_dist(center);
+        if first == second {
+            assert_eq!(pair[0].manhattan_dist(pair[1]), 1);
+        } else {
+            assert_eq!(first + 1, second);
+        }
+    }
+}
+
+#[test]
+fn spiral_clipped() {
+    let center = Vec2 { x: 0u8, y: 1 };
+    let points: Vec<_> = center.spiral(2).collect();
+    assert_eq!(
+        points,
+        vec![
+            Vec2 { x: 0, y: 1 },
+            Vec2 { x: 0, y: 0 },
+            Vec2 { x: 1, y: 0 },
+            Vec2 { x: 1, y: 1 },
+            Vec2 { x: 1, y: 2 },
+            Vec2 { x: 0, y: 2 },
+            Vec2 { x: 2, y: 0 },
+            Vec2 { x: 2, y: 1 },
+            Vec2 { x: 2, y: 2 },
+            Vec2 { x: 2, y: 3 },
+            Vec2 { x: 1, y: 3 },
+            Vec2 { x: 0, y: 3 },
+        ]
+    );
+
+    let rect = Rect { start: Vec2 { x: 5, y: 5 }, size: Vec2 { x: 2, y: 1 } };
+    let points: Vec<_> = center.spiral(u8::MAX).clip_to(&rect).collect();
+    assert_eq!(points, vec![Vec2 { x: 5, y: 5 }, Vec2 { x: 6, y: 5 }]);
+
+    let points: Vec<_> =
+        Vec2 { x: 6u8, y: 5 }.spiral(1).clip_to(&rect).collect();
+    assert_eq!(points, vec![Vec2 { x: 6, y: 5 }, Vec2 { x: 5, y: 5 }]);
+}